@@ -0,0 +1,47 @@
+#!/bin/bash
+# USAGE: scripts/check_inject.sh [template ...]
+#
+# Injects the given templates (in order) into a copy of template.rs and builds the result,
+# to make sure snippets that share dependencies can be used together in one solution.
+# Without arguments, checks the combinations listed in COMBINATIONS.
+
+COMBINATIONS=(
+    "src/template/data_structures/segment_tree.rs src/template/data_structures/lazy_segment_tree.rs src/template/data_structures/dynamic_segment_tree.rs"
+    "src/template/data_structures/segment_tree_monoid.rs src/template/data_structures/lazy_segment_tree_monoid.rs src/template/data_structures/dynamic_segment_tree.rs"
+    "src/template/data_structures/dynamic_lazy_segment_tree.rs src/template/data_structures/segment_tree.rs src/template/data_structures/lazy_segment_tree_monoid.rs src/template/data_structures/dynamic_segment_tree.rs"
+    "src/template/data_structures/persistent_segment_tree.rs src/template/data_structures/segment_tree_monoid.rs src/template/data_structures/dynamic_segment_tree.rs"
+    "src/template/data_structures/sparse_table.rs src/template/data_structures/segment_tree.rs"
+    "src/template/data_structures/sparse_table_monoid.rs src/template/data_structures/segment_tree_monoid.rs"
+    "src/template/data_structures/implicit_treap.rs src/template/data_structures/lazy_segment_tree.rs src/template/data_structures/segment_tree.rs"
+    "src/template/data_structures/implicit_treap_monoid.rs src/template/data_structures/lazy_segment_tree_monoid.rs src/template/data_structures/dynamic_lazy_segment_tree.rs"
+    "src/template/graph/weighted.rs src/template/data_structures/mergeable_heap.rs"
+    "src/template/data_structures/mergeable_heap.rs src/template/graph/weighted.rs"
+)
+
+ROOT="$(cd "$(dirname "$0")/.." && pwd)"
+WORK="$ROOT/target/check_inject"
+
+check() {
+    mkdir -p "$WORK/src"
+    {
+        printf '[package]\nname = "check_inject"\nversion = "0.1.0"\nedition = "2024"\n\n[workspace]\n\n[dependencies]\n'
+        sed -n '/^\[workspace.dependencies\]/,/^\[/p' "$ROOT/Cargo.toml" | grep -E '^[a-z]'
+    } > "$WORK/Cargo.toml"
+    if [ -f "$ROOT/Cargo.lock" ]; then cp "$ROOT/Cargo.lock" "$WORK/Cargo.lock"; fi
+    cp "$ROOT/template.rs" "$WORK/src/main.rs"
+
+    # INJECT paths are relative to the repository root
+    (cd "$ROOT" && for t in "$@"; do python3 scripts/inject.py "$t" "$WORK/src/main.rs" > /dev/null || exit 1; done) || return 1
+    cargo build --quiet $CARGO_FLAGS --manifest-path "$WORK/Cargo.toml" 2>&1 | grep -E "^error" -A5
+    [ "${PIPESTATUS[0]}" -eq 0 ]
+}
+
+status=0
+if [ $# -gt 0 ]; then
+    check "$@" || status=1
+else
+    for combo in "${COMBINATIONS[@]}"; do
+        if check $combo; then echo "OK:   $combo"; else echo "FAIL: $combo"; status=1; fi
+    done
+fi
+exit $status
@@ -8,6 +8,17 @@ if len(sys.argv) < 3:
 template_path = sys.argv[1]
 target_file = sys.argv[2]
 
+if not os.path.exists(target_file):
+    print(f"Error: Target file '{target_file}' not found")
+    sys.exit(1)
+
+with open(target_file, "r", encoding="utf-8") as f:
+    target_lines = f.readlines()
+
+# Snippets already injected by earlier calls are skipped, so that shared dependencies
+# (e.g. monoid.rs) are defined only once when several templates are injected into one file.
+existing = "".join(target_lines)
+
 def extract_snippet(filepath, visited=None):
     if visited is None:
         visited = set()
@@ -25,6 +36,7 @@ def extract_snippet(filepath, visited=None):
         lines = f.readlines()
 
     snippet = []
+    own = []
     in_snippet = False
 
     for line in lines:
@@ -42,22 +54,22 @@ def extract_snippet(filepath, visited=None):
             break
         
         if in_snippet:
-            snippet.append(line)
+            own.append(line)
 
-    return snippet
+    if own and "".join(own) in existing:
+        own = []
+    return snippet + own
 
 snippet = extract_snippet(template_path)
 
-if not snippet:
-    print(f"Error: No snippet markers found or snippet is empty in {template_path}")
-    sys.exit(1)
-
-if not os.path.exists(target_file):
-    print(f"Error: Target file '{target_file}' not found")
-    sys.exit(1)
+with open(template_path, "r", encoding="utf-8") as f:
+    if "// --- SNAP START ---" not in f.read():
+        print(f"Error: No snippet markers found in {template_path}")
+        sys.exit(1)
 
-with open(target_file, "r", encoding="utf-8") as f:
-    target_lines = f.readlines()
+if not snippet:
+    print(f"'{template_path}' (and dependencies) already injected into {target_file}")
+    sys.exit(0)
 
 insert_idx = -1
 for i, line in enumerate(target_lines):
@@ -0,0 +1,303 @@
+#![allow(dead_code)]
+
+// --- SNAP START ---
+
+/// Binary Trie (Multiset of Fixed-Width Integers for XOR Queries)
+///
+/// Stores a multiset of `bits`-bit unsigned integers, most significant bit first.
+/// Unlike `Trie` (character based), every query can be taken "after xor-ing with `x`":
+/// `min_xor`, `max_xor`, `kth_smallest_with_xor`.
+///
+/// `xor_all(x)` lazily replaces every element `y` with `y ^ x` in O(1).
+///
+/// # Examples
+///
+/// ```
+/// let mut trie = BinaryTrie::new(30);
+/// for x in [3, 10, 5, 10] {
+///     trie.insert(x);
+/// }
+/// assert_eq!(trie.count(10), 2);
+/// assert_eq!(trie.min_xor(6), Some(3)); // 6 ^ 5
+/// assert_eq!(trie.max_xor(6), Some(12)); // 6 ^ 10
+/// assert_eq!(trie.kth_smallest_with_xor(1, 0), Some(5)); // sorted [3, 5, 10, 10]
+///
+/// trie.xor_all(1); // {2, 11, 4, 11}
+/// assert_eq!(trie.kth_smallest(0), Some(2));
+/// assert!(trie.erase_one(11));
+/// assert_eq!(trie.count(11), 1);
+/// ```
+///
+/// # Complexity
+/// - All operations: O(bits)
+#[derive(Debug, Clone)]
+pub struct BinaryTrie {
+    bits: usize,
+    // children[v][b]: child of v for bit b (0 if absent; the root is never a child)
+    children: Vec<[usize; 2]>,
+    // count[v]: number of elements in the subtree of v
+    count: Vec<usize>,
+    mask: u64,
+}
+
+impl BinaryTrie {
+    /// Creates an empty trie for values in `[0, 2^bits)`.
+    ///
+    /// # Panics
+    /// Panics if `bits` is not in `1..=64`.
+    pub fn new(bits: usize) -> Self {
+        assert!((1..=64).contains(&bits), "bits must be in 1..=64");
+        BinaryTrie {
+            bits,
+            children: vec![[0; 2]],
+            count: vec![0],
+            mask: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.count[0]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn check(
+        &self,
+        x: u64,
+    ) {
+        assert!(self.bits == 64 || x >> self.bits == 0, "Value out of range");
+    }
+
+    /// Inserts `x`.
+    pub fn insert(
+        &mut self,
+        x: u64,
+    ) {
+        self.check(x);
+        let x = x ^ self.mask;
+        let mut v = 0;
+        self.count[v] += 1;
+        for i in (0..self.bits).rev() {
+            let b = ((x >> i) & 1) as usize;
+            if self.children[v][b] == 0 {
+                self.children.push([0; 2]);
+                self.count.push(0);
+                self.children[v][b] = self.children.len() - 1;
+            }
+            v = self.children[v][b];
+            self.count[v] += 1;
+        }
+    }
+
+    /// Returns the leaf node of `x`, if present.
+    fn find(
+        &self,
+        x: u64,
+    ) -> Option<usize> {
+        self.check(x);
+        let x = x ^ self.mask;
+        let mut v = 0;
+        for i in (0..self.bits).rev() {
+            let b = ((x >> i) & 1) as usize;
+            v = self.children[v][b];
+            if v == 0 || self.count[v] == 0 {
+                return None;
+            }
+        }
+        Some(v)
+    }
+
+    /// Returns the number of occurrences of `x`.
+    pub fn count(
+        &self,
+        x: u64,
+    ) -> usize {
+        self.find(x).map_or(0, |v| self.count[v])
+    }
+
+    pub fn contains(
+        &self,
+        x: u64,
+    ) -> bool {
+        self.count(x) > 0
+    }
+
+    /// Removes one occurrence of `x`. Returns `false` if `x` is not contained.
+    pub fn erase_one(
+        &mut self,
+        x: u64,
+    ) -> bool {
+        if self.find(x).is_none() {
+            return false;
+        }
+        let x = x ^ self.mask;
+        let mut v = 0;
+        self.count[v] -= 1;
+        for i in (0..self.bits).rev() {
+            v = self.children[v][((x >> i) & 1) as usize];
+            self.count[v] -= 1;
+        }
+        true
+    }
+
+    /// Replaces every element `y` with `y ^ x`.
+    ///
+    /// # Complexity
+    /// - O(1)
+    pub fn xor_all(
+        &mut self,
+        x: u64,
+    ) {
+        self.check(x);
+        self.mask ^= x;
+    }
+
+    /// Returns the `k`-th (0-indexed) smallest value of `y ^ x` over all elements `y`.
+    pub fn kth_smallest_with_xor(
+        &self,
+        mut k: usize,
+        x: u64,
+    ) -> Option<u64> {
+        self.check(x);
+        if k >= self.len() {
+            return None;
+        }
+        let x = x ^ self.mask;
+        let mut v = 0;
+        let mut res = 0;
+        for i in (0..self.bits).rev() {
+            let b = ((x >> i) & 1) as usize;
+            // Going to child b makes this bit of the result 0
+            let c = self.children[v][b];
+            let cnt = if c == 0 { 0 } else { self.count[c] };
+            if k < cnt {
+                v = c;
+            } else {
+                k -= cnt;
+                res |= 1 << i;
+                v = self.children[v][b ^ 1];
+            }
+        }
+        Some(res)
+    }
+
+    /// Returns the minimum of `y ^ x` over all elements `y`.
+    pub fn min_xor(
+        &self,
+        x: u64,
+    ) -> Option<u64> {
+        self.kth_smallest_with_xor(0, x)
+    }
+
+    /// Returns the maximum of `y ^ x` over all elements `y`.
+    pub fn max_xor(
+        &self,
+        x: u64,
+    ) -> Option<u64> {
+        self.kth_smallest_with_xor(self.len().checked_sub(1)?, x)
+    }
+
+    /// Returns the `k`-th (0-indexed) smallest element.
+    pub fn kth_smallest(
+        &self,
+        k: usize,
+    ) -> Option<u64> {
+        self.kth_smallest_with_xor(k, 0)
+    }
+
+    /// Returns the number of elements `y` with `y ^ x < upper`.
+    pub fn count_less_with_xor(
+        &self,
+        upper: u64,
+        x: u64,
+    ) -> usize {
+        self.check(x);
+        if self.bits < 64 && upper >> self.bits != 0 {
+            return self.len();
+        }
+        let x = x ^ self.mask;
+        let mut v = 0;
+        let mut res = 0;
+        for i in (0..self.bits).rev() {
+            let b = ((x >> i) & 1) as usize;
+            if (upper >> i) & 1 == 1 {
+                let c = self.children[v][b];
+                if c != 0 {
+                    res += self.count[c];
+                }
+                v = self.children[v][b ^ 1];
+            } else {
+                v = self.children[v][b];
+            }
+            if v == 0 {
+                break;
+            }
+        }
+        res
+    }
+}
+
+// --- SNAP END ---
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_binary_trie_random() {
+        let mut seed: u64 = 0xC0FF_EE12_3456_789A;
+        let mut next = |m: u64| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed % m
+        };
+        let bits = 6;
+        let mut trie = BinaryTrie::new(bits);
+        let mut naive: Vec<u64> = Vec::new();
+        for _ in 0..5000 {
+            let x = next(1 << bits);
+            match next(5) {
+                0 | 1 => {
+                    trie.insert(x);
+                    naive.push(x);
+                }
+                2 => {
+                    let pos = naive.iter().position(|&y| y == x);
+                    if let Some(pos) = pos {
+                        naive.swap_remove(pos);
+                    }
+                    assert_eq!(trie.erase_one(x), pos.is_some());
+                }
+                3 => {
+                    trie.xor_all(x);
+                    naive.iter_mut().for_each(|y| *y ^= x);
+                }
+                _ => {
+                    let mut xored: Vec<u64> = naive.iter().map(|&y| y ^ x).collect();
+                    xored.sort();
+                    assert_eq!(trie.min_xor(x), xored.first().copied());
+                    assert_eq!(trie.max_xor(x), xored.last().copied());
+                    let k = next(naive.len() as u64 + 1) as usize;
+                    assert_eq!(trie.kth_smallest_with_xor(k, x), xored.get(k).copied());
+                    let upper = next(1 << (bits + 1));
+                    assert_eq!(trie.count_less_with_xor(upper, x), xored.iter().filter(|&&y| y < upper).count());
+                    assert_eq!(trie.count(x), naive.iter().filter(|&&y| y == x).count());
+                }
+            }
+            assert_eq!(trie.len(), naive.len());
+        }
+    }
+
+    #[test]
+    fn test_binary_trie_full_width() {
+        let mut trie = BinaryTrie::new(64);
+        trie.insert(u64::MAX);
+        trie.insert(0);
+        assert_eq!(trie.max_xor(1), Some(u64::MAX - 1));
+        assert_eq!(trie.min_xor(u64::MAX - 1), Some(1));
+        assert_eq!(trie.count_less_with_xor(u64::MAX, 0), 1);
+    }
+}
@@ -0,0 +1,325 @@
+#![allow(dead_code)]
+use std::collections::VecDeque;
+
+// --- SNAP START ---
+
+/// Convex Hull Trick (Monotone Slopes, Deque)
+///
+/// Maintains the lower (or upper) envelope of lines `y = a * x + b` whose slopes are added monotonically:
+/// each new slope must be either <= all current slopes or >= all current slopes.
+/// Speeds up DP transitions of the form `dp[i] = min_j (a_j * x_i + b_j)`.
+///
+/// Use `LiChaoTree` when slopes arrive in arbitrary order.
+///
+/// Lines are evaluated in i128, so only the answer itself has to fit in i64.
+/// In max mode, lines are stored negated, so `a` and `b` must not be `i64::MIN`.
+///
+/// # Examples
+///
+/// ```
+/// let mut cht = ConvexHullTrick::new_min();
+/// cht.add_line(2, 0); // y = 2x
+/// cht.add_line(0, 1); // y = 1
+/// cht.add_line(-1, 5); // y = -x + 5
+/// assert_eq!(cht.query(-1), Some(-2));
+/// assert_eq!(cht.query(2), Some(1));
+/// assert_eq!(cht.query(10), Some(-5));
+///
+/// // When query points are increasing, `query_monotone_inc` runs in amortized O(1)
+/// assert_eq!(cht.query_monotone_inc(0), Some(0));
+/// assert_eq!(cht.query_monotone_inc(6), Some(-1));
+/// ```
+///
+/// # Complexity
+/// - `add_line`: amortized O(1)
+/// - `query`: O(log N)
+/// - `query_monotone_inc` / `query_monotone_dec`: amortized O(1)
+#[derive(Debug, Clone)]
+pub struct ConvexHullTrick {
+    // Lines of the lower envelope (after negation in max mode), slopes strictly decreasing
+    lines: VecDeque<(i64, i64)>,
+    is_max: bool,
+}
+
+impl ConvexHullTrick {
+    /// Creates a container that answers minimum queries.
+    pub fn new_min() -> Self {
+        ConvexHullTrick {
+            lines: VecDeque::new(),
+            is_max: false,
+        }
+    }
+
+    /// Creates a container that answers maximum queries.
+    pub fn new_max() -> Self {
+        ConvexHullTrick {
+            lines: VecDeque::new(),
+            is_max: true,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    // With a1 > a2 > a3, l2 is unnecessary if l1 and l3 cross at or before l1 and l2 do.
+    fn is_redundant(
+        l1: (i64, i64),
+        l2: (i64, i64),
+        l3: (i64, i64),
+    ) -> bool {
+        let (a1, b1) = (l1.0 as i128, l1.1 as i128);
+        let (a2, b2) = (l2.0 as i128, l2.1 as i128);
+        let (a3, b3) = (l3.0 as i128, l3.1 as i128);
+        // (b2 - b1) * (a1 - a3) >= (b3 - b1) * (a1 - a2), where each product can reach 2^128:
+        // compare the signs, then the magnitudes in u128
+        let lhs = (b2 - b1 >= 0, (b2 - b1).unsigned_abs() * (a1 - a3) as u128);
+        let rhs = (b3 - b1 >= 0, (b3 - b1).unsigned_abs() * (a1 - a2) as u128);
+        match (lhs.0, rhs.0) {
+            (true, false) => true,
+            (false, true) => false,
+            (true, true) => lhs.1 >= rhs.1,
+            (false, false) => lhs.1 <= rhs.1,
+        }
+    }
+
+    /// Adds the line `y = a * x + b`.
+    ///
+    /// # Panics
+    /// Panics if `a` is strictly between the current minimum and maximum slopes,
+    /// or if `a` or `b` is `i64::MIN` in max mode.
+    pub fn add_line(
+        &mut self,
+        a: i64,
+        b: i64,
+    ) {
+        let line = if self.is_max {
+            assert!(a != i64::MIN && b != i64::MIN, "a and b must not be i64::MIN in max mode");
+            (-a, -b)
+        } else {
+            (a, b)
+        };
+        let (a, b) = line;
+        let (front, back) = match (self.lines.front(), self.lines.back()) {
+            (Some(&f), Some(&l)) => (f, l),
+            _ => {
+                self.lines.push_back(line);
+                return;
+            }
+        };
+        if a <= back.0 {
+            if a == back.0 {
+                if back.1 <= b {
+                    return;
+                }
+                self.lines.pop_back();
+            }
+            while self.lines.len() >= 2 {
+                let n = self.lines.len();
+                if Self::is_redundant(self.lines[n - 2], self.lines[n - 1], line) {
+                    self.lines.pop_back();
+                } else {
+                    break;
+                }
+            }
+            self.lines.push_back(line);
+        } else if a >= front.0 {
+            if a == front.0 {
+                if front.1 <= b {
+                    return;
+                }
+                self.lines.pop_front();
+            }
+            while self.lines.len() >= 2 {
+                if Self::is_redundant(line, self.lines[0], self.lines[1]) {
+                    self.lines.pop_front();
+                } else {
+                    break;
+                }
+            }
+            self.lines.push_front(line);
+        } else {
+            panic!("Slopes must be added monotonically");
+        }
+    }
+
+    fn eval(
+        &self,
+        i: usize,
+        x: i64,
+    ) -> i128 {
+        let (a, b) = self.lines[i];
+        a as i128 * x as i128 + b as i128
+    }
+
+    fn output(
+        &self,
+        v: i128,
+    ) -> i64 {
+        let v = if self.is_max { -v } else { v };
+        i64::try_from(v).expect("The answer does not fit in i64")
+    }
+
+    /// Returns the minimum (or maximum) value at `x`, or `None` if empty.
+    ///
+    /// # Panics
+    /// Panics if the answer overflows i64.
+    pub fn query(
+        &self,
+        x: i64,
+    ) -> Option<i64> {
+        if self.lines.is_empty() {
+            return None;
+        }
+        // The optimal line is the first i with eval(i) <= eval(i + 1)
+        let (mut lo, mut hi) = (0, self.lines.len() - 1);
+        while lo < hi {
+            let mid = (lo + hi) / 2;
+            if self.eval(mid, x) <= self.eval(mid + 1, x) {
+                hi = mid;
+            } else {
+                lo = mid + 1;
+            }
+        }
+        Some(self.output(self.eval(lo, x)))
+    }
+
+    /// Same as `query`, but assumes `x` is not less than any previously queried `x`.
+    /// Lines that can no longer be optimal are discarded.
+    pub fn query_monotone_inc(
+        &mut self,
+        x: i64,
+    ) -> Option<i64> {
+        if self.lines.is_empty() {
+            return None;
+        }
+        while self.lines.len() >= 2 && self.eval(0, x) >= self.eval(1, x) {
+            self.lines.pop_front();
+        }
+        Some(self.output(self.eval(0, x)))
+    }
+
+    /// Same as `query`, but assumes `x` is not greater than any previously queried `x`.
+    /// Lines that can no longer be optimal are discarded.
+    pub fn query_monotone_dec(
+        &mut self,
+        x: i64,
+    ) -> Option<i64> {
+        if self.lines.is_empty() {
+            return None;
+        }
+        while self.lines.len() >= 2 {
+            let n = self.lines.len();
+            if self.eval(n - 1, x) >= self.eval(n - 2, x) {
+                self.lines.pop_back();
+            } else {
+                break;
+            }
+        }
+        Some(self.output(self.eval(self.lines.len() - 1, x)))
+    }
+}
+
+// --- SNAP END ---
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convex_hull_trick_random() {
+        let mut seed: u64 = 0x2545_F491_4F6C_DD1D;
+        let mut next = |m: u64| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed % m
+        };
+        for is_max in [false, true] {
+            for _ in 0..50 {
+                let mut cht = if is_max { ConvexHullTrick::new_max() } else { ConvexHullTrick::new_min() };
+                let mut lines: Vec<(i64, i64)> = Vec::new();
+                let (mut lo, mut hi) = (0i64, 0i64);
+                for step in 0..30 {
+                    // Grow the slope range on either side
+                    let a = if step == 0 {
+                        0
+                    } else if next(2) == 0 {
+                        lo -= next(3) as i64;
+                        lo
+                    } else {
+                        hi += next(3) as i64;
+                        hi
+                    };
+                    let b = next(101) as i64 - 50;
+                    cht.add_line(a, b);
+                    lines.push((a, b));
+                    for x in -20..=20 {
+                        let values = lines.iter().map(|&(a, b)| a * x + b);
+                        let expected = if is_max { values.max() } else { values.min() };
+                        assert_eq!(cht.query(x), expected);
+                    }
+                }
+                let brute = |x: i64| {
+                    let values = lines.iter().map(|&(a, b)| a * x + b);
+                    if is_max {
+                        values.max()
+                    } else {
+                        values.min()
+                    }
+                };
+                let mut inc = cht.clone();
+                for x in -20..=20 {
+                    assert_eq!(inc.query_monotone_inc(x), brute(x));
+                }
+                for x in (-20..=20).rev() {
+                    assert_eq!(cht.query_monotone_dec(x), brute(x));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_convex_hull_trick_large_values() {
+        // a * x overflows i64, but the answers fit
+        let (a, b, x) = (10_000_000_000, -9_000_000_000_000_000_000, 1_000_000_000);
+        let mut min = ConvexHullTrick::new_min();
+        let mut max = ConvexHullTrick::new_max();
+        for (a, b) in [(a, b), (-a, -b)] {
+            min.add_line(a, b);
+            max.add_line(a, b);
+        }
+        assert_eq!(min.query(x), Some(-1_000_000_000_000_000_000));
+        assert_eq!(max.query(x), Some(1_000_000_000_000_000_000));
+        assert_eq!(max.query_monotone_dec(x / 2), Some(4_000_000_000_000_000_000));
+    }
+
+    #[test]
+    fn test_convex_hull_trick_full_range_lines() {
+        let mut seed: u64 = 0x2545_F491_4F6C_DD1D;
+        let mut next = || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            (seed as i64).max(-i64::MAX)
+        };
+        for _ in 0..200 {
+            let mut lines: Vec<(i64, i64)> = (0..8).map(|_| (next(), next())).collect();
+            lines.sort_by(|p, q| q.cmp(p));
+            let mut cht = ConvexHullTrick::new_min();
+            for &(a, b) in &lines {
+                cht.add_line(a, b);
+            }
+            for x in -1..=1 {
+                let expected = lines.iter().map(|&(a, b)| a as i128 * x as i128 + b as i128).min().unwrap();
+                if let Ok(expected) = i64::try_from(expected) {
+                    assert_eq!(cht.query(x), Some(expected));
+                }
+            }
+        }
+    }
+}
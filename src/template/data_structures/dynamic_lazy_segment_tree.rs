@@ -0,0 +1,463 @@
+#![allow(dead_code)]
+
+// INJECT: src/template/data_structures/monoid.rs
+
+// --- SNAP START ---
+
+/// Dynamic Lazy Segment Tree (Range Update, Range Query)
+///
+/// A lazy segment tree over a sparse coordinate range such as `[0, 10^18)`.
+/// Nodes are allocated on demand, so each operation allocates O(log W) nodes, where W is the range width.
+///
+/// `MM` is any `MapMonoid` (see `monoid.rs`). By default every position holds the identity element;
+/// use `with_init` when untouched positions must hold another value
+/// (e.g. `(0, 1)` for `RangeAddRangeSum`, where the length must be known).
+///
+/// # Examples
+///
+/// ```
+/// // Range Add + Range Min over [0, 10^18), all zeros initially
+/// let mut st = DynamicLazySegTree::<RangeAddRangeMin<i64>>::with_init(0, 1_000_000_000_000_000_000, |_, _| 0);
+/// st.apply_range(10, 1_000_000_000_000, 5);
+/// st.apply_range(500, 600, -3);
+/// assert_eq!(st.prod(0, 20), 0);
+/// assert_eq!(st.prod(400, 1_000), 2);
+///
+/// // Range Add + Range Sum: each untouched range [l, r) holds (0, r - l)
+/// let mut st = DynamicLazySegTree::<RangeAddRangeSum<i64>>::with_init(0, 1_000_000_000, |l, r| (0, r - l));
+/// st.apply_range(0, 1_000_000_000, 1);
+/// assert_eq!(st.prod(10, 20).0, 10);
+/// ```
+pub struct DynamicLazySegTree<MM: MapMonoid> {
+    range_l: i64,
+    range_r: i64,
+    nodes: Vec<LazyNode<<MM::M as Monoid>::S, MM::F>>,
+    init: Box<dyn Fn(i64, i64) -> <MM::M as Monoid>::S>,
+}
+
+struct LazyNode<S, F> {
+    value: S,
+    lazy: F,
+    left: Option<usize>,
+    right: Option<usize>,
+}
+
+impl<MM: MapMonoid> DynamicLazySegTree<MM>
+where
+    <MM::M as Monoid>::S: 'static,
+{
+    /// Creates a new DynamicLazySegTree for the range `[range_l, range_r)` where every position holds the identity.
+    ///
+    /// # Complexity
+    /// - O(1)
+    pub fn new(
+        range_l: i64,
+        range_r: i64,
+    ) -> Self {
+        Self::with_init(range_l, range_r, |_, _| MM::identity_element())
+    }
+
+    /// Creates a new DynamicLazySegTree for the range `[range_l, range_r)`.
+    ///
+    /// # Arguments
+    /// - `init`: `init(l, r)` returns the product of the untouched range `[l, r)`.
+    ///
+    /// # Complexity
+    /// - O(1)
+    pub fn with_init<I>(
+        range_l: i64,
+        range_r: i64,
+        init: I,
+    ) -> Self
+    where
+        I: Fn(i64, i64) -> <MM::M as Monoid>::S + 'static,
+    {
+        assert!(range_l < range_r);
+        let root = LazyNode {
+            value: init(range_l, range_r),
+            lazy: MM::identity_map(),
+            left: None,
+            right: None,
+        };
+        Self {
+            range_l,
+            range_r,
+            nodes: vec![root],
+            init: Box::new(init),
+        }
+    }
+
+    /// Sets the element at `p` to `x`.
+    ///
+    /// # Complexity
+    /// - O(log W)
+    pub fn set(
+        &mut self,
+        p: i64,
+        x: <MM::M as Monoid>::S,
+    ) {
+        assert!(self.range_l <= p && p < self.range_r);
+        self.set_recursive(0, self.range_l, self.range_r, p, x);
+    }
+
+    /// Gets the element at `p`.
+    ///
+    /// # Complexity
+    /// - O(log W)
+    pub fn get(
+        &mut self,
+        p: i64,
+    ) -> <MM::M as Monoid>::S {
+        assert!(self.range_l <= p && p < self.range_r);
+        self.prod(p, p + 1)
+    }
+
+    /// Applies `f` to every element in `[l, r)`.
+    ///
+    /// # Complexity
+    /// - O(log W)
+    pub fn apply_range(
+        &mut self,
+        l: i64,
+        r: i64,
+        f: MM::F,
+    ) {
+        let l = l.max(self.range_l);
+        let r = r.min(self.range_r);
+        if l >= r {
+            return;
+        }
+        self.apply_recursive(0, self.range_l, self.range_r, l, r, &f);
+    }
+
+    /// Returns the product over `[l, r)`.
+    /// Returns the identity element if the range is empty or outside the tree's range.
+    ///
+    /// # Complexity
+    /// - O(log W)
+    pub fn prod(
+        &mut self,
+        l: i64,
+        r: i64,
+    ) -> <MM::M as Monoid>::S {
+        let l = l.max(self.range_l);
+        let r = r.min(self.range_r);
+        if l >= r {
+            return MM::identity_element();
+        }
+        self.prod_recursive(0, self.range_l, self.range_r, l, r)
+    }
+
+    /// Returns the product over the whole range.
+    pub fn all_prod(&self) -> <MM::M as Monoid>::S {
+        self.nodes[0].value.clone()
+    }
+
+    /// Returns the largest `r` in `[l, range_r]` such that `g(prod(l, r))` is true.
+    ///
+    /// `g` must be monotonic and `g(identity)` must be true.
+    ///
+    /// # Complexity
+    /// - O(log W)
+    pub fn max_right<G>(
+        &mut self,
+        l: i64,
+        g: G,
+    ) -> i64
+    where
+        G: Fn(&<MM::M as Monoid>::S) -> bool,
+    {
+        assert!(self.range_l <= l && l <= self.range_r);
+        assert!(g(&MM::identity_element()));
+        let mut acc = MM::identity_element();
+        self.max_right_recursive(0, self.range_l, self.range_r, l, &g, &mut acc)
+            .unwrap_or(self.range_r)
+    }
+
+    /// Returns the smallest `l` in `[range_l, r]` such that `g(prod(l, r))` is true.
+    ///
+    /// `g` must be monotonic and `g(identity)` must be true.
+    ///
+    /// # Complexity
+    /// - O(log W)
+    pub fn min_left<G>(
+        &mut self,
+        r: i64,
+        g: G,
+    ) -> i64
+    where
+        G: Fn(&<MM::M as Monoid>::S) -> bool,
+    {
+        assert!(self.range_l <= r && r <= self.range_r);
+        assert!(g(&MM::identity_element()));
+        let mut acc = MM::identity_element();
+        self.min_left_recursive(0, self.range_l, self.range_r, r, &g, &mut acc)
+            .unwrap_or(self.range_l)
+    }
+
+    fn new_node(
+        &mut self,
+        l: i64,
+        r: i64,
+    ) -> usize {
+        self.nodes.push(LazyNode {
+            value: (self.init)(l, r),
+            lazy: MM::identity_map(),
+            left: None,
+            right: None,
+        });
+        self.nodes.len() - 1
+    }
+
+    fn all_apply(
+        &mut self,
+        k: usize,
+        f: &MM::F,
+    ) {
+        let node = &mut self.nodes[k];
+        node.value = MM::mapping(f, &node.value);
+        node.lazy = MM::composition(f, &node.lazy);
+    }
+
+    /// Creates both children if needed and pushes the lazy map down.
+    fn push(
+        &mut self,
+        k: usize,
+        l: i64,
+        r: i64,
+    ) -> (usize, usize) {
+        let mid = l + (r - l) / 2;
+        let left = match self.nodes[k].left {
+            Some(c) => c,
+            None => {
+                let c = self.new_node(l, mid);
+                self.nodes[k].left = Some(c);
+                c
+            }
+        };
+        let right = match self.nodes[k].right {
+            Some(c) => c,
+            None => {
+                let c = self.new_node(mid, r);
+                self.nodes[k].right = Some(c);
+                c
+            }
+        };
+        let lazy = std::mem::replace(&mut self.nodes[k].lazy, MM::identity_map());
+        self.all_apply(left, &lazy);
+        self.all_apply(right, &lazy);
+        (left, right)
+    }
+
+    fn pull(
+        &mut self,
+        k: usize,
+        left: usize,
+        right: usize,
+    ) {
+        self.nodes[k].value = MM::binary_operation(&self.nodes[left].value, &self.nodes[right].value);
+    }
+
+    fn set_recursive(
+        &mut self,
+        k: usize,
+        l: i64,
+        r: i64,
+        p: i64,
+        x: <MM::M as Monoid>::S,
+    ) {
+        if r - l == 1 {
+            self.nodes[k].value = x;
+            return;
+        }
+        let mid = l + (r - l) / 2;
+        let (left, right) = self.push(k, l, r);
+        if p < mid {
+            self.set_recursive(left, l, mid, p, x);
+        } else {
+            self.set_recursive(right, mid, r, p, x);
+        }
+        self.pull(k, left, right);
+    }
+
+    fn apply_recursive(
+        &mut self,
+        k: usize,
+        l: i64,
+        r: i64,
+        ql: i64,
+        qr: i64,
+        f: &MM::F,
+    ) {
+        if qr <= l || r <= ql {
+            return;
+        }
+        if ql <= l && r <= qr {
+            self.all_apply(k, f);
+            return;
+        }
+        let mid = l + (r - l) / 2;
+        let (left, right) = self.push(k, l, r);
+        self.apply_recursive(left, l, mid, ql, qr, f);
+        self.apply_recursive(right, mid, r, ql, qr, f);
+        self.pull(k, left, right);
+    }
+
+    fn prod_recursive(
+        &mut self,
+        k: usize,
+        l: i64,
+        r: i64,
+        ql: i64,
+        qr: i64,
+    ) -> <MM::M as Monoid>::S {
+        if qr <= l || r <= ql {
+            return MM::identity_element();
+        }
+        if ql <= l && r <= qr {
+            return self.nodes[k].value.clone();
+        }
+        let mid = l + (r - l) / 2;
+        let (left, right) = self.push(k, l, r);
+        let left_val = self.prod_recursive(left, l, mid, ql, qr);
+        let right_val = self.prod_recursive(right, mid, r, ql, qr);
+        MM::binary_operation(&left_val, &right_val)
+    }
+
+    fn max_right_recursive<G>(
+        &mut self,
+        k: usize,
+        l: i64,
+        r: i64,
+        ql: i64,
+        g: &G,
+        acc: &mut <MM::M as Monoid>::S,
+    ) -> Option<i64>
+    where
+        G: Fn(&<MM::M as Monoid>::S) -> bool,
+    {
+        if r <= ql {
+            return None;
+        }
+        if ql <= l {
+            let merged = MM::binary_operation(acc, &self.nodes[k].value);
+            if g(&merged) {
+                *acc = merged;
+                return None;
+            }
+            if r - l == 1 {
+                return Some(l);
+            }
+        }
+        let mid = l + (r - l) / 2;
+        let (left, right) = self.push(k, l, r);
+        if let Some(res) = self.max_right_recursive(left, l, mid, ql, g, acc) {
+            return Some(res);
+        }
+        self.max_right_recursive(right, mid, r, ql, g, acc)
+    }
+
+    fn min_left_recursive<G>(
+        &mut self,
+        k: usize,
+        l: i64,
+        r: i64,
+        qr: i64,
+        g: &G,
+        acc: &mut <MM::M as Monoid>::S,
+    ) -> Option<i64>
+    where
+        G: Fn(&<MM::M as Monoid>::S) -> bool,
+    {
+        if qr <= l {
+            return None;
+        }
+        if r <= qr {
+            let merged = MM::binary_operation(&self.nodes[k].value, acc);
+            if g(&merged) {
+                *acc = merged;
+                return None;
+            }
+            if r - l == 1 {
+                return Some(r);
+            }
+        }
+        let mid = l + (r - l) / 2;
+        let (left, right) = self.push(k, l, r);
+        if let Some(res) = self.min_left_recursive(right, mid, r, qr, g, acc) {
+            return Some(res);
+        }
+        self.min_left_recursive(left, l, mid, qr, g, acc)
+    }
+}
+
+// --- SNAP END ---
+
+#[allow(unused_imports)]
+use super::monoid::{MapMonoid, Monoid};
+
+#[cfg(test)]
+mod tests {
+    use super::super::monoid::{RangeAddRangeMin, RangeAddRangeSum, RangeAffineRangeSum};
+    use super::*;
+
+    #[test]
+    fn test_dynamic_lazy_segment_tree_small() {
+        // Compare with a naive array on a small range with a negative offset
+        let (lo, hi) = (-7i64, 13i64);
+        let mut naive = vec![0i64; (hi - lo) as usize];
+        let mut st = DynamicLazySegTree::<RangeAddRangeSum<i64>>::with_init(lo, hi, |l, r| (0, r - l));
+        let ops = [(-7, 13, 2), (-3, 4, -5), (0, 1, 7), (5, 13, 3), (-7, -6, 1)];
+        for &(l, r, x) in &ops {
+            st.apply_range(l, r, x);
+            for i in l..r {
+                naive[(i - lo) as usize] += x;
+            }
+            for a in lo..=hi {
+                for b in a..=hi {
+                    let expected: i64 = naive[(a - lo) as usize..(b - lo) as usize].iter().sum();
+                    assert_eq!(st.prod(a, b).0, expected);
+                }
+            }
+        }
+        st.set(2, (100, 1));
+        assert_eq!(st.get(2).0, 100);
+
+        // max_right / min_left over prefix sums
+        for l in lo..=hi {
+            for limit in [0, 3, 10, 30] {
+                let r = st.max_right(l, |s| s.0 <= limit);
+                assert!(st.prod(l, r).0 <= limit);
+                assert!(r == hi || st.prod(l, r + 1).0 > limit);
+            }
+        }
+        for r in lo..=hi {
+            for limit in [0, 3, 10, 30] {
+                let l = st.min_left(r, |s| s.0 <= limit);
+                assert!(st.prod(l, r).0 <= limit);
+                assert!(l == lo || st.prod(l - 1, r).0 > limit);
+            }
+        }
+    }
+
+    #[test]
+    fn test_dynamic_lazy_segment_tree_large() {
+        let big = 1_000_000_000_000_000_000i64;
+        let mut st = DynamicLazySegTree::<RangeAddRangeMin<i64>>::with_init(0, big, |_, _| 0);
+        st.apply_range(10, big, 5);
+        st.apply_range(big / 2, big / 2 + 100, -3);
+        assert_eq!(st.prod(0, 20), 0);
+        assert_eq!(st.prod(20, big), 2);
+        assert_eq!(st.all_prod(), 0);
+        // First position >= 10 whose value is < 5
+        assert_eq!(st.max_right(10, |&x| x >= 5), big / 2);
+        assert_eq!(st.min_left(big, |&x| x >= 5), big / 2 + 100);
+
+        // Range assign via affine (0, c)
+        let mut st = DynamicLazySegTree::<RangeAffineRangeSum<i64>>::with_init(0, big, |l, r| (0, r - l));
+        st.apply_range(0, big, (0, 1));
+        st.apply_range(3, 5, (0, 10));
+        assert_eq!(st.prod(0, 10).0, 28);
+        assert!(st.nodes.len() < 1000);
+    }
+}
@@ -1,18 +1,8 @@
 #![allow(dead_code)]
 
-// --- SNAP START ---
+// INJECT: src/template/data_structures/monoid.rs
 
-/// Trait for Monoid, used in Segment Tree.
-///
-/// A monoid is a set with an associative binary operation and an identity element.
-pub trait Monoid {
-    /// The type of the elements in the monoid.
-    type S: Copy;
-    /// The identity element of the monoid.
-    fn identity() -> Self::S;
-    /// The associative binary operation.
-    fn binary_operation(a: &Self::S, b: &Self::S) -> Self::S;
-}
+// --- SNAP START ---
 
 /// Dynamic Segment Tree (Point Update, Range Query)
 ///
@@ -20,6 +10,8 @@ pub trait Monoid {
 /// in O(log N) time, where N is the coordinate range. Unlike a standard segment tree,
 /// it allocates nodes on-demand, making it suitable for very large ranges (e.g., 0 to 10^18).
 ///
+/// `M` is any `Monoid` (see `monoid.rs`), e.g. `DynamicSegTree::<Sum<i64>>`.
+///
 /// # Examples
 ///
 /// ```
@@ -121,8 +113,8 @@ impl<M: Monoid> DynamicSegTree<M> {
             self.update_recursive(right_child, mid, node_r, target_idx, val);
         }
 
-        let left_val = self.nodes[node_idx].left.map_or(M::identity(), |i| self.nodes[i].value);
-        let right_val = self.nodes[node_idx].right.map_or(M::identity(), |i| self.nodes[i].value);
+        let left_val = self.nodes[node_idx].left.map_or(M::identity(), |i| self.nodes[i].value.clone());
+        let right_val = self.nodes[node_idx].right.map_or(M::identity(), |i| self.nodes[i].value.clone());
         self.nodes[node_idx].value = M::binary_operation(&left_val, &right_val);
     }
 
@@ -131,7 +123,7 @@ impl<M: Monoid> DynamicSegTree<M> {
             return M::identity();
         }
         if l <= node_l && node_r <= r {
-            return self.nodes[node_idx].value;
+            return self.nodes[node_idx].value.clone();
         }
 
         let mid = node_l + (node_r - node_l) / 2;
@@ -178,4 +170,9 @@ impl<M: Monoid> DynamicSegTree<M> {
             new_idx
         }
     }
-}
\ No newline at end of file
+}
+
+// --- SNAP END ---
+
+#[allow(unused_imports)]
+use super::monoid::Monoid;
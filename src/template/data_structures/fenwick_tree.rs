@@ -0,0 +1,479 @@
+#![allow(dead_code)]
+
+// --- SNAP START ---
+
+use std::ops::{Add, Mul, Sub};
+
+/// Fenwick Tree / Binary Indexed Tree (Point Add, Prefix Sum)
+///
+/// Supports point additions and prefix/range sums over an abelian group in O(log N).
+/// Lighter than `SegmentTree` when the operation is invertible (sum, xor over `+`-like types, ModInt).
+///
+/// # Generics
+/// - `T`: Element type. `T::default()` must be the additive identity (`0` for integers and ModInt).
+///
+/// # Examples
+///
+/// ```
+/// let mut bit = FenwickTree::<i64>::new(5);
+/// bit.add(0, 3);
+/// bit.add(2, 5);
+/// bit.add(4, 1);
+///
+/// assert_eq!(bit.sum(3), 8); // [0, 3)
+/// assert_eq!(bit.range_sum(2, 5), 6); // [2, 5)
+///
+/// // Smallest `r` such that the prefix sum of [0, r] reaches 4 (all elements must be non-negative)
+/// assert_eq!(bit.lower_bound(4), 2);
+///
+/// // Inversion count
+/// let a = vec![3, 1, 2, 0];
+/// let mut bit = FenwickTree::<i64>::new(4);
+/// let mut inv = 0;
+/// for (i, &x) in a.iter().enumerate() {
+///     inv += i as i64 - bit.sum(x + 1);
+///     bit.add(x, 1);
+/// }
+/// assert_eq!(inv, 5);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FenwickTree<T> {
+    n: usize,
+    tree: Vec<T>,
+}
+
+impl<T> FenwickTree<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    /// Creates a new FenwickTree of size `n` filled with `T::default()`.
+    ///
+    /// # Complexity
+    /// - O(N)
+    pub fn new(n: usize) -> Self {
+        FenwickTree {
+            n,
+            tree: vec![T::default(); n + 1],
+        }
+    }
+
+    /// Creates a new FenwickTree from the given data.
+    ///
+    /// # Complexity
+    /// - O(N)
+    pub fn from_slice(data: &[T]) -> Self {
+        let n = data.len();
+        let mut tree = vec![T::default(); n + 1];
+        for i in 1..=n {
+            tree[i] = tree[i] + data[i - 1];
+            let j = i + (i & i.wrapping_neg());
+            if j <= n {
+                tree[j] = tree[j] + tree[i];
+            }
+        }
+        FenwickTree { n, tree }
+    }
+
+    /// Returns the number of elements.
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Adds `x` to the element at index `p`.
+    ///
+    /// # Panics
+    /// Panics if `p` is out of bounds.
+    ///
+    /// # Complexity
+    /// - O(log N)
+    pub fn add(
+        &mut self,
+        p: usize,
+        x: T,
+    ) {
+        assert!(p < self.n, "Index out of bounds");
+        let mut i = p + 1;
+        while i <= self.n {
+            self.tree[i] = self.tree[i] + x;
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    /// Returns the sum of the range `[0, r)`.
+    ///
+    /// # Panics
+    /// Panics if `r > n`.
+    ///
+    /// # Complexity
+    /// - O(log N)
+    pub fn sum(
+        &self,
+        r: usize,
+    ) -> T {
+        assert!(r <= self.n, "Index out of bounds");
+        let mut res = T::default();
+        let mut i = r;
+        while i > 0 {
+            res = res + self.tree[i];
+            i -= i & i.wrapping_neg();
+        }
+        res
+    }
+
+    /// Returns the sum of the range `[l, r)`.
+    ///
+    /// # Panics
+    /// Panics if `l > r` or `r > n`.
+    ///
+    /// # Complexity
+    /// - O(log N)
+    pub fn range_sum(
+        &self,
+        l: usize,
+        r: usize,
+    ) -> T {
+        assert!(l <= r && r <= self.n, "Invalid range");
+        self.sum(r) - self.sum(l)
+    }
+
+    /// Gets the current value at index `p`.
+    ///
+    /// # Complexity
+    /// - O(log N)
+    pub fn get(
+        &self,
+        p: usize,
+    ) -> T {
+        self.range_sum(p, p + 1)
+    }
+
+    /// Sets the element at index `p` to `x`.
+    ///
+    /// # Complexity
+    /// - O(log N)
+    pub fn set(
+        &mut self,
+        p: usize,
+        x: T,
+    ) {
+        let cur = self.get(p);
+        self.add(p, x - cur);
+    }
+}
+
+impl<T> FenwickTree<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T> + PartialOrd,
+{
+    /// Returns the smallest index `r` such that `sum(r + 1) >= w`,
+    /// i.e. the position where the prefix sum first reaches `w`.
+    /// Returns `n` if the total sum is less than `w`.
+    ///
+    /// All elements must be non-negative (the prefix sums must be monotonic).
+    /// Typical use: finding the k-th smallest element of a multiset stored as counts
+    /// (`lower_bound(k + 1)` for 0-indexed `k`).
+    ///
+    /// # Complexity
+    /// - O(log N)
+    pub fn lower_bound(
+        &self,
+        w: T,
+    ) -> usize {
+        if w <= T::default() {
+            return 0;
+        }
+        let mut w = w;
+        let mut pos = 0;
+        let mut step = 1;
+        while step * 2 <= self.n {
+            step *= 2;
+        }
+        while step > 0 {
+            if pos + step <= self.n && self.tree[pos + step] < w {
+                w = w - self.tree[pos + step];
+                pos += step;
+            }
+            step /= 2;
+        }
+        pos
+    }
+}
+
+/// Fenwick Tree with Range Add and Range Sum
+///
+/// Maintains two BITs `b0` and `b1` such that `sum(r) = b0.sum(r) + b1.sum(r) * r`.
+///
+/// # Examples
+///
+/// ```
+/// let mut bit = RangeFenwickTree::<i64>::new(5);
+/// bit.add(1, 4, 2); // [0, 2, 2, 2, 0]
+/// bit.add(0, 2, 1); // [1, 3, 2, 2, 0]
+/// assert_eq!(bit.sum(0, 5), 8);
+/// assert_eq!(bit.sum(1, 3), 5);
+/// assert_eq!(bit.get(3), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RangeFenwickTree<T> {
+    n: usize,
+    b0: FenwickTree<T>,
+    b1: FenwickTree<T>,
+}
+
+impl<T> RangeFenwickTree<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + From<i64>,
+{
+    /// Creates a new RangeFenwickTree of size `n` filled with `T::default()`.
+    pub fn new(n: usize) -> Self {
+        RangeFenwickTree {
+            n,
+            b0: FenwickTree::new(n + 1),
+            b1: FenwickTree::new(n + 1),
+        }
+    }
+
+    /// Creates a new RangeFenwickTree from the given data.
+    ///
+    /// # Complexity
+    /// - O(N)
+    pub fn from_slice(data: &[T]) -> Self {
+        let n = data.len();
+        let mut init = data.to_vec();
+        init.push(T::default());
+        RangeFenwickTree {
+            n,
+            b0: FenwickTree::from_slice(&init),
+            b1: FenwickTree::new(n + 1),
+        }
+    }
+
+    /// Adds `x` to every element in the range `[l, r)`.
+    ///
+    /// # Complexity
+    /// - O(log N)
+    pub fn add(
+        &mut self,
+        l: usize,
+        r: usize,
+        x: T,
+    ) {
+        assert!(l <= r && r <= self.n, "Invalid range");
+        let lx = T::from(l as i64) * x;
+        let rx = T::from(r as i64) * x;
+        self.b0.add(l, T::default() - lx);
+        self.b0.add(r, rx);
+        self.b1.add(l, x);
+        self.b1.add(r, T::default() - x);
+    }
+
+    /// Returns the sum of the range `[0, r)`.
+    fn prefix_sum(
+        &self,
+        r: usize,
+    ) -> T {
+        self.b0.sum(r) + self.b1.sum(r) * T::from(r as i64)
+    }
+
+    /// Returns the sum of the range `[l, r)`.
+    ///
+    /// # Complexity
+    /// - O(log N)
+    pub fn sum(
+        &self,
+        l: usize,
+        r: usize,
+    ) -> T {
+        assert!(l <= r && r <= self.n, "Invalid range");
+        self.prefix_sum(r) - self.prefix_sum(l)
+    }
+
+    /// Gets the current value at index `p`.
+    ///
+    /// # Complexity
+    /// - O(log N)
+    pub fn get(
+        &self,
+        p: usize,
+    ) -> T {
+        self.sum(p, p + 1)
+    }
+}
+
+/// 2D Fenwick Tree (Point Add, Rectangle Sum)
+///
+/// # Examples
+///
+/// ```
+/// let mut bit = FenwickTree2D::<i64>::new(3, 4);
+/// bit.add(0, 0, 1);
+/// bit.add(1, 2, 5);
+/// bit.add(2, 3, 7);
+/// assert_eq!(bit.sum(2, 3), 6); // [0, 2) x [0, 3)
+/// assert_eq!(bit.rect_sum(1, 3, 2, 4), 12); // [1, 3) x [2, 4)
+/// ```
+#[derive(Debug, Clone)]
+pub struct FenwickTree2D<T> {
+    h: usize,
+    w: usize,
+    tree: Vec<Vec<T>>,
+}
+
+impl<T> FenwickTree2D<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    /// Creates a new `h` x `w` FenwickTree2D filled with `T::default()`.
+    ///
+    /// # Complexity
+    /// - O(HW)
+    pub fn new(
+        h: usize,
+        w: usize,
+    ) -> Self {
+        FenwickTree2D {
+            h,
+            w,
+            tree: vec![vec![T::default(); w + 1]; h + 1],
+        }
+    }
+
+    /// Adds `x` to the element at `(i, j)`.
+    ///
+    /// # Complexity
+    /// - O(log H log W)
+    pub fn add(
+        &mut self,
+        i: usize,
+        j: usize,
+        x: T,
+    ) {
+        assert!(i < self.h && j < self.w, "Index out of bounds");
+        let mut a = i + 1;
+        while a <= self.h {
+            let mut b = j + 1;
+            while b <= self.w {
+                self.tree[a][b] = self.tree[a][b] + x;
+                b += b & b.wrapping_neg();
+            }
+            a += a & a.wrapping_neg();
+        }
+    }
+
+    /// Returns the sum of the rectangle `[0, i) x [0, j)`.
+    ///
+    /// # Complexity
+    /// - O(log H log W)
+    pub fn sum(
+        &self,
+        i: usize,
+        j: usize,
+    ) -> T {
+        assert!(i <= self.h && j <= self.w, "Index out of bounds");
+        let mut res = T::default();
+        let mut a = i;
+        while a > 0 {
+            let mut b = j;
+            while b > 0 {
+                res = res + self.tree[a][b];
+                b -= b & b.wrapping_neg();
+            }
+            a -= a & a.wrapping_neg();
+        }
+        res
+    }
+
+    /// Returns the sum of the rectangle `[i1, i2) x [j1, j2)`.
+    ///
+    /// # Complexity
+    /// - O(log H log W)
+    pub fn rect_sum(
+        &self,
+        i1: usize,
+        i2: usize,
+        j1: usize,
+        j2: usize,
+    ) -> T {
+        assert!(i1 <= i2 && j1 <= j2, "Invalid range");
+        self.sum(i2, j2) - self.sum(i1, j2) - self.sum(i2, j1) + self.sum(i1, j1)
+    }
+}
+
+// --- SNAP END ---
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fenwick_tree() {
+        let data = vec![5, 3, 7, 9, 6, 4, 1, 2];
+        let mut bit = FenwickTree::from_slice(&data);
+        for l in 0..=data.len() {
+            for r in l..=data.len() {
+                assert_eq!(bit.range_sum(l, r), data[l..r].iter().sum::<i64>());
+            }
+        }
+        bit.add(3, -4);
+        bit.set(0, 1);
+        assert_eq!(bit.get(3), 5);
+        assert_eq!(bit.sum(8), 29);
+    }
+
+    #[test]
+    fn test_lower_bound() {
+        // multiset {1, 1, 4, 6}
+        let mut bit = FenwickTree::<i64>::new(7);
+        for x in [1, 1, 4, 6] {
+            bit.add(x, 1);
+        }
+        assert_eq!(bit.lower_bound(0), 0);
+        assert_eq!(bit.lower_bound(1), 1);
+        assert_eq!(bit.lower_bound(2), 1);
+        assert_eq!(bit.lower_bound(3), 4);
+        assert_eq!(bit.lower_bound(4), 6);
+        assert_eq!(bit.lower_bound(5), 7);
+    }
+
+    #[test]
+    fn test_range_fenwick_tree() {
+        let mut naive = vec![1i64, 2, 3, 4, 5, 6];
+        let mut bit = RangeFenwickTree::from_slice(&naive);
+        let ops = [(0, 3, 2), (2, 6, -1), (1, 2, 10), (0, 6, 3)];
+        for &(l, r, x) in &ops {
+            bit.add(l, r, x);
+            for v in naive.iter_mut().take(r).skip(l) {
+                *v += x;
+            }
+            for a in 0..=6 {
+                for b in a..=6 {
+                    assert_eq!(bit.sum(a, b), naive[a..b].iter().sum::<i64>());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_fenwick_tree_2d() {
+        let mut bit = FenwickTree2D::<i64>::new(3, 3);
+        let mut naive = [[0i64; 3]; 3];
+        for (i, j, x) in [(0, 0, 1), (1, 1, 2), (2, 0, 3), (1, 2, 4), (0, 0, 5)] {
+            bit.add(i, j, x);
+            naive[i][j] += x;
+        }
+        for i1 in 0..=3 {
+            for i2 in i1..=3 {
+                for j1 in 0..=3 {
+                    for j2 in j1..=3 {
+                        let expected: i64 = (i1..i2).map(|i| naive[i][j1..j2].iter().sum::<i64>()).sum();
+                        assert_eq!(bit.rect_sum(i1, i2, j1, j2), expected);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,470 @@
+#![allow(dead_code)]
+
+// --- SNAP START ---
+
+/// Implicit Treap (Balanced Sequence with Split / Merge, Range Reverse and Lazy Action)
+///
+/// Stores a sequence and supports insert/erase at any position, range reverse, range rotate,
+/// range product and range action in expected O(log N).
+///
+/// Uses the same conventions as `LazySegmentTree`:
+/// `op(T, T) -> T`, identity `e`, `mapping(x, f) -> T`, `composition(new_f, old_f) -> U` and identity map `id`.
+/// The products of both directions are maintained, so `reverse` also works for non-commutative `op`.
+///
+/// # Examples
+///
+/// ```
+/// // Range Add + Range Sum on (sum, len)
+/// let data: Vec<(i64, i64)> = vec![1, 2, 3, 4, 5].into_iter().map(|x| (x, 1)).collect();
+/// let mut tr = ImplicitTreap::new(
+///     &data,
+///     |a: (i64, i64), b: (i64, i64)| (a.0 + b.0, a.1 + b.1),
+///     (0, 0),
+///     |x: (i64, i64), f: i64| (x.0 + f * x.1, x.1),
+///     |f: i64, g: i64| f + g,
+///     0,
+/// );
+/// tr.reverse(1, 4); // [1, 4, 3, 2, 5]
+/// tr.apply(0, 2, 10); // [11, 14, 3, 2, 5]
+/// tr.insert(5, (7, 1)); // [11, 14, 3, 2, 5, 7]
+/// assert_eq!(tr.erase(2).0, 3); // [11, 14, 2, 5, 7]
+/// tr.rotate(0, 2, 5); // [2, 5, 7, 11, 14]
+/// assert_eq!(tr.prod(1, 4).0, 23);
+/// assert_eq!(tr.to_vec().iter().map(|x| x.0).collect::<Vec<_>>(), vec![2, 5, 7, 11, 14]);
+///
+/// // Or with a `MapMonoid` (inject `implicit_treap_monoid.rs`)
+/// let mut tr = ImplicitTreap::from_map_monoid::<RangeAddRangeMin<i64>>(&[3, 1, 4]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ImplicitTreap<T, U, F, M, C> {
+    nodes: Vec<TreapNode<T, U>>,
+    root: usize,
+    op: F,
+    e: T,
+    mapping: M,
+    composition: C,
+    id: U,
+    seed: u64,
+}
+
+#[derive(Debug, Clone)]
+struct TreapNode<T, U> {
+    val: T,
+    sum: T,
+    rev_sum: T,
+    lazy: U,
+    rev: bool,
+    left: usize,
+    right: usize,
+    size: usize,
+    priority: u64,
+}
+
+impl<T, U, F, M, C> ImplicitTreap<T, U, F, M, C>
+where
+    T: Copy + Clone + std::fmt::Debug,
+    U: Copy + Clone + std::fmt::Debug + PartialEq,
+    F: Fn(T, T) -> T,
+    M: Fn(T, U) -> T,
+    C: Fn(U, U) -> U,
+{
+    /// # Complexity
+    /// - O(N log N) expected
+    pub fn new(
+        data: &[T],
+        op: F,
+        e: T,
+        mapping: M,
+        composition: C,
+        id: U,
+    ) -> Self {
+        // Node 0 is the null node
+        let null = TreapNode {
+            val: e,
+            sum: e,
+            rev_sum: e,
+            lazy: id,
+            rev: false,
+            left: 0,
+            right: 0,
+            size: 0,
+            priority: 0,
+        };
+        let mut treap = ImplicitTreap {
+            nodes: vec![null],
+            root: 0,
+            op,
+            e,
+            mapping,
+            composition,
+            id,
+            seed: 0x9E37_79B9_7F4A_7C15,
+        };
+        for &x in data {
+            let node = treap.new_node(x);
+            treap.root = treap.merge(treap.root, node);
+        }
+        treap
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes[self.root].size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root == 0
+    }
+
+    /// Inserts `x` so that it becomes the element at position `pos`.
+    pub fn insert(
+        &mut self,
+        pos: usize,
+        x: T,
+    ) {
+        assert!(pos <= self.len(), "Index out of bounds");
+        let (a, b) = self.split(self.root, pos);
+        let node = self.new_node(x);
+        let a = self.merge(a, node);
+        self.root = self.merge(a, b);
+    }
+
+    /// Removes and returns the element at position `pos`.
+    pub fn erase(
+        &mut self,
+        pos: usize,
+    ) -> T {
+        assert!(pos < self.len(), "Index out of bounds");
+        let (a, b) = self.split(self.root, pos);
+        let (mid, c) = self.split(b, 1);
+        self.root = self.merge(a, c);
+        self.nodes[mid].val
+    }
+
+    /// Gets the element at position `pos`.
+    pub fn get(
+        &mut self,
+        pos: usize,
+    ) -> T {
+        self.prod(pos, pos + 1)
+    }
+
+    /// Sets the element at position `pos` to `x`.
+    pub fn set(
+        &mut self,
+        pos: usize,
+        x: T,
+    ) {
+        assert!(pos < self.len(), "Index out of bounds");
+        let (a, b) = self.split(self.root, pos);
+        let (mid, c) = self.split(b, 1);
+        self.nodes[mid].val = x;
+        self.update(mid);
+        let a = self.merge(a, mid);
+        self.root = self.merge(a, c);
+    }
+
+    /// Returns the product over `[l, r)`.
+    pub fn prod(
+        &mut self,
+        l: usize,
+        r: usize,
+    ) -> T {
+        assert!(l <= r && r <= self.len(), "Invalid range");
+        let (a, b) = self.split(self.root, l);
+        let (mid, c) = self.split(b, r - l);
+        let res = self.nodes[mid].sum;
+        let b = self.merge(mid, c);
+        self.root = self.merge(a, b);
+        res
+    }
+
+    pub fn all_prod(&self) -> T {
+        self.nodes[self.root].sum
+    }
+
+    /// Applies `f` to every element in `[l, r)`.
+    pub fn apply(
+        &mut self,
+        l: usize,
+        r: usize,
+        f: U,
+    ) {
+        assert!(l <= r && r <= self.len(), "Invalid range");
+        let (a, b) = self.split(self.root, l);
+        let (mid, c) = self.split(b, r - l);
+        self.all_apply(mid, f);
+        let b = self.merge(mid, c);
+        self.root = self.merge(a, b);
+    }
+
+    /// Reverses `[l, r)`.
+    pub fn reverse(
+        &mut self,
+        l: usize,
+        r: usize,
+    ) {
+        assert!(l <= r && r <= self.len(), "Invalid range");
+        let (a, b) = self.split(self.root, l);
+        let (mid, c) = self.split(b, r - l);
+        self.toggle(mid);
+        let b = self.merge(mid, c);
+        self.root = self.merge(a, b);
+    }
+
+    /// Rotates `[l, r)` so that the element at `m` becomes the first (like `slice::rotate_left(m - l)`).
+    pub fn rotate(
+        &mut self,
+        l: usize,
+        m: usize,
+        r: usize,
+    ) {
+        assert!(l <= m && m <= r && r <= self.len(), "Invalid range");
+        let (a, b) = self.split(self.root, l);
+        let (x, c) = self.split(b, r - l);
+        let (x1, x2) = self.split(x, m - l);
+        let x = self.merge(x2, x1);
+        let b = self.merge(x, c);
+        self.root = self.merge(a, b);
+    }
+
+    /// Returns the current sequence.
+    pub fn to_vec(&self) -> Vec<T> {
+        let mut res = Vec::with_capacity(self.len());
+        self.collect(self.root, self.id, false, &mut res);
+        res
+    }
+
+    /// In-order traversal without pushing: `f` and `flip` are the pending action and reversal of the ancestors.
+    fn collect(
+        &self,
+        t: usize,
+        f: U,
+        flip: bool,
+        res: &mut Vec<T>,
+    ) {
+        if t == 0 {
+            return;
+        }
+        let node = &self.nodes[t];
+        let (l, r) = if flip { (node.right, node.left) } else { (node.left, node.right) };
+        let child_f = (self.composition)(f, node.lazy);
+        self.collect(l, child_f, flip ^ node.rev, res);
+        res.push((self.mapping)(node.val, f));
+        self.collect(r, child_f, flip ^ node.rev, res);
+    }
+
+    /// Splits the tree rooted at `t` into the first `k` elements and the rest.
+    /// Returns the roots of both parts. Node `0` is the empty tree.
+    pub fn split(
+        &mut self,
+        t: usize,
+        k: usize,
+    ) -> (usize, usize) {
+        if t == 0 {
+            return (0, 0);
+        }
+        self.push(t);
+        let left = self.nodes[t].left;
+        if k <= self.nodes[left].size {
+            let (a, b) = self.split(left, k);
+            self.nodes[t].left = b;
+            self.update(t);
+            (a, t)
+        } else {
+            let right = self.nodes[t].right;
+            let (a, b) = self.split(right, k - self.nodes[left].size - 1);
+            self.nodes[t].right = a;
+            self.update(t);
+            (t, b)
+        }
+    }
+
+    /// Merges the trees rooted at `a` and `b` (all elements of `a` come first) and returns the new root.
+    pub fn merge(
+        &mut self,
+        a: usize,
+        b: usize,
+    ) -> usize {
+        if a == 0 {
+            return b;
+        }
+        if b == 0 {
+            return a;
+        }
+        if self.nodes[a].priority > self.nodes[b].priority {
+            self.push(a);
+            let right = self.nodes[a].right;
+            self.nodes[a].right = self.merge(right, b);
+            self.update(a);
+            a
+        } else {
+            self.push(b);
+            let left = self.nodes[b].left;
+            self.nodes[b].left = self.merge(a, left);
+            self.update(b);
+            b
+        }
+    }
+
+    fn new_node(
+        &mut self,
+        x: T,
+    ) -> usize {
+        // xorshift64
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 7;
+        self.seed ^= self.seed << 17;
+        self.nodes.push(TreapNode {
+            val: x,
+            sum: x,
+            rev_sum: x,
+            lazy: self.id,
+            rev: false,
+            left: 0,
+            right: 0,
+            size: 1,
+            priority: self.seed,
+        });
+        self.nodes.len() - 1
+    }
+
+    fn update(
+        &mut self,
+        t: usize,
+    ) {
+        let (l, r) = (self.nodes[t].left, self.nodes[t].right);
+        let val = self.nodes[t].val;
+        self.nodes[t].size = self.nodes[l].size + self.nodes[r].size + 1;
+        self.nodes[t].sum = (self.op)((self.op)(self.nodes[l].sum, val), self.nodes[r].sum);
+        self.nodes[t].rev_sum = (self.op)((self.op)(self.nodes[r].rev_sum, val), self.nodes[l].rev_sum);
+    }
+
+    fn all_apply(
+        &mut self,
+        t: usize,
+        f: U,
+    ) {
+        if t == 0 {
+            return;
+        }
+        let node = &mut self.nodes[t];
+        node.val = (self.mapping)(node.val, f);
+        node.sum = (self.mapping)(node.sum, f);
+        node.rev_sum = (self.mapping)(node.rev_sum, f);
+        node.lazy = (self.composition)(f, node.lazy);
+    }
+
+    fn toggle(
+        &mut self,
+        t: usize,
+    ) {
+        if t == 0 {
+            return;
+        }
+        let node = &mut self.nodes[t];
+        std::mem::swap(&mut node.left, &mut node.right);
+        std::mem::swap(&mut node.sum, &mut node.rev_sum);
+        node.rev ^= true;
+    }
+
+    fn push(
+        &mut self,
+        t: usize,
+    ) {
+        let (l, r) = (self.nodes[t].left, self.nodes[t].right);
+        if self.nodes[t].lazy != self.id {
+            let f = self.nodes[t].lazy;
+            self.all_apply(l, f);
+            self.all_apply(r, f);
+            self.nodes[t].lazy = self.id;
+        }
+        if self.nodes[t].rev {
+            self.toggle(l);
+            self.toggle(r);
+            self.nodes[t].rev = false;
+        }
+    }
+}
+
+// --- SNAP END ---
+
+#[cfg(test)]
+mod tests {
+    use super::super::monoid::{Affine, Monoid, RangeAddRangeMin};
+    use super::*;
+
+    #[test]
+    fn test_implicit_treap_random() {
+        let mut seed: u64 = 0x1234_5678_9ABC_DEF1;
+        let mut next = |m: u64| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed % m
+        };
+        let mut naive: Vec<i64> = (0..10).map(|i| i * 3 % 7).collect();
+        let mut tr = ImplicitTreap::from_map_monoid::<RangeAddRangeMin<i64>>(&naive);
+        for _ in 0..3000 {
+            let n = naive.len() as u64;
+            let mut l = next(n + 1) as usize;
+            let mut r = next(n + 1) as usize;
+            if l > r {
+                std::mem::swap(&mut l, &mut r);
+            }
+            match next(7) {
+                0 => {
+                    let (p, x) = (next(n + 1) as usize, next(20) as i64);
+                    tr.insert(p, x);
+                    naive.insert(p, x);
+                }
+                1 if n > 0 => {
+                    let p = next(n) as usize;
+                    assert_eq!(tr.erase(p), naive.remove(p));
+                }
+                2 => {
+                    tr.reverse(l, r);
+                    naive[l..r].reverse();
+                }
+                3 => {
+                    let x = next(11) as i64 - 5;
+                    tr.apply(l, r, x);
+                    naive[l..r].iter_mut().for_each(|v| *v += x);
+                }
+                4 => {
+                    let m = l + next((r - l) as u64 + 1) as usize;
+                    tr.rotate(l, m, r);
+                    naive[l..r].rotate_left(m - l);
+                }
+                _ => {
+                    let expected = naive[l..r].iter().copied().min().unwrap_or(i64::MAX);
+                    assert_eq!(tr.prod(l, r), expected);
+                }
+            }
+            assert_eq!(tr.len(), naive.len());
+        }
+        assert_eq!(tr.to_vec(), naive);
+    }
+
+    #[test]
+    fn test_implicit_treap_non_commutative_reverse() {
+        let fs: Vec<(i64, i64)> = vec![(2, 1), (3, 0), (1, 5), (2, -1)];
+        let mut tr = ImplicitTreap::new(
+            &fs,
+            |a, b| Affine::<i64>::binary_operation(&a, &b),
+            (1, 0),
+            |x, _: ()| x,
+            |_, _| (),
+            (),
+        );
+        tr.reverse(0, 4);
+        let expected = fs
+            .iter()
+            .rev()
+            .fold((1, 0), |f, g| Affine::<i64>::binary_operation(&f, g));
+        assert_eq!(tr.all_prod(), expected);
+        tr.reverse(1, 3);
+        let order = [fs[3], fs[1], fs[2], fs[0]];
+        let expected = order.iter().fold((1, 0), |f, g| Affine::<i64>::binary_operation(&f, g));
+        assert_eq!(tr.prod(0, 4), expected);
+    }
+}
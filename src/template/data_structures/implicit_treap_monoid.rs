@@ -0,0 +1,35 @@
+#![allow(dead_code)]
+
+// INJECT: src/template/data_structures/implicit_treap.rs
+// INJECT: src/template/data_structures/monoid.rs
+
+// --- SNAP START ---
+
+impl<T, U> ImplicitTreap<T, U, fn(T, T) -> T, fn(T, U) -> T, fn(U, U) -> U>
+where
+    T: Copy + Clone + std::fmt::Debug,
+    U: Copy + Clone + std::fmt::Debug + PartialEq,
+{
+    /// Creates a new ImplicitTreap from the given data, using the operations of `MM`.
+    pub fn from_map_monoid<MM>(data: &[T]) -> Self
+    where
+        MM: MapMonoid<F = U>,
+        MM::M: Monoid<S = T>,
+    {
+        Self::new(
+            data,
+            |a, b| MM::binary_operation(&a, &b),
+            MM::identity_element(),
+            |x, f| MM::mapping(&f, &x),
+            |new_op, old_op| MM::composition(&new_op, &old_op),
+            MM::identity_map(),
+        )
+    }
+}
+
+// --- SNAP END ---
+
+#[allow(unused_imports)]
+use super::implicit_treap::ImplicitTreap;
+#[allow(unused_imports)]
+use super::monoid::{MapMonoid, Monoid};
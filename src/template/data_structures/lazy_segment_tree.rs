@@ -65,6 +65,17 @@
 ///     (1, 0)
 /// );
 /// ```
+///
+/// ## 4. Using a `MapMonoid` implementation
+/// Inject `lazy_segment_tree_monoid.rs` for `from_map_monoid`.
+/// Ready-made map monoids are in `monoid.rs` (`RangeAddRangeMin`, `RangeAddRangeSum`, `RangeAffineRangeSum`, ...).
+/// ```ignore
+/// let mut st = LazySegmentTree::from_map_monoid::<RangeAddRangeMin<i64>>(&data);
+/// st.apply_range(1, 3, 10);
+///
+/// // As a struct field
+/// struct Solver { st: MapMonoidLazySegmentTree<RangeAddRangeMin<i64>> }
+/// ```
 #[derive(Debug, Clone)]
 pub struct LazySegmentTree<T, U, F, M, C> {
     n: usize,
@@ -389,3 +400,5 @@ where
         self.lazy[k] = self.id;
     }
 }
+
+// --- SNAP END ---
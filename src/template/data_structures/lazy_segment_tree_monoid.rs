@@ -0,0 +1,47 @@
+#![allow(dead_code)]
+
+// INJECT: src/template/data_structures/lazy_segment_tree.rs
+// INJECT: src/template/data_structures/monoid.rs
+
+// --- SNAP START ---
+
+/// Lazy Segment Tree built from a `MapMonoid` implementation.
+pub type MapMonoidLazySegmentTree<MM> = LazySegmentTree<
+    <<MM as MapMonoid>::M as Monoid>::S,
+    <MM as MapMonoid>::F,
+    fn(<<MM as MapMonoid>::M as Monoid>::S, <<MM as MapMonoid>::M as Monoid>::S) -> <<MM as MapMonoid>::M as Monoid>::S,
+    fn(<<MM as MapMonoid>::M as Monoid>::S, <MM as MapMonoid>::F) -> <<MM as MapMonoid>::M as Monoid>::S,
+    fn(<MM as MapMonoid>::F, <MM as MapMonoid>::F) -> <MM as MapMonoid>::F,
+>;
+
+impl<T, U> LazySegmentTree<T, U, fn(T, T) -> T, fn(T, U) -> T, fn(U, U) -> U>
+where
+    T: Copy + Clone + std::fmt::Debug,
+    U: Copy + Clone + std::fmt::Debug + PartialEq,
+{
+    /// Creates a new LazySegmentTree from the given data, using the operations of `MM`.
+    ///
+    /// # Complexity
+    /// - O(N)
+    pub fn from_map_monoid<MM>(data: &[T]) -> Self
+    where
+        MM: MapMonoid<F = U>,
+        MM::M: Monoid<S = T>,
+    {
+        Self::new(
+            data,
+            |a, b| MM::binary_operation(&a, &b),
+            MM::identity_element(),
+            |x, f| MM::mapping(&f, &x),
+            |new_op, old_op| MM::composition(&new_op, &old_op),
+            MM::identity_map(),
+        )
+    }
+}
+
+// --- SNAP END ---
+
+#[allow(unused_imports)]
+use super::lazy_segment_tree::LazySegmentTree;
+#[allow(unused_imports)]
+use super::monoid::{MapMonoid, Monoid};
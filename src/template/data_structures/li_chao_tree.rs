@@ -0,0 +1,508 @@
+#![allow(dead_code)]
+
+// --- SNAP START ---
+
+/// Li Chao Tree (Line / Segment Container over Known x-Coordinates)
+///
+/// Maintains a set of lines `y = a * x + b` and answers the minimum (or maximum) value at a point.
+/// The query points must be given in advance; use `DynamicLiChaoTree` when they are not.
+///
+/// Lines are evaluated in i128, so only the answer itself has to fit in i64.
+/// In max mode, lines are stored negated, so `a` and `b` must not be `i64::MIN`.
+///
+/// # Examples
+///
+/// ```
+/// let xs = vec![-2, 0, 1, 3, 5];
+/// let mut lct = LiChaoTree::new_min(&xs);
+/// lct.add_line(1, 0); // y = x
+/// lct.add_line(-1, 2); // y = -x + 2
+/// assert_eq!(lct.query(0), Some(0));
+/// assert_eq!(lct.query(3), Some(-1));
+///
+/// // y = -3 only on x in [0, 2)
+/// lct.add_segment(0, -3, 0, 2);
+/// assert_eq!(lct.query(1), Some(-3));
+/// assert_eq!(lct.query(3), Some(-1));
+///
+/// let mut lct = LiChaoTree::new_max(&xs);
+/// assert_eq!(lct.query(0), None);
+/// lct.add_line(2, 1);
+/// assert_eq!(lct.query(5), Some(11));
+/// ```
+///
+/// # Complexity
+/// - `add_line`, `query`: O(log N)
+/// - `add_segment`: O(log^2 N)
+#[derive(Debug, Clone)]
+pub struct LiChaoTree {
+    xs: Vec<i64>,
+    lines: Vec<Option<(i64, i64)>>,
+    is_max: bool,
+}
+
+impl LiChaoTree {
+    /// Creates a tree that answers minimum queries at the points `xs`.
+    pub fn new_min(xs: &[i64]) -> Self {
+        Self::new(xs, false)
+    }
+
+    /// Creates a tree that answers maximum queries at the points `xs`.
+    pub fn new_max(xs: &[i64]) -> Self {
+        Self::new(xs, true)
+    }
+
+    fn new(
+        xs: &[i64],
+        is_max: bool,
+    ) -> Self {
+        let mut xs = xs.to_vec();
+        xs.sort();
+        xs.dedup();
+        let n = xs.len().max(1);
+        LiChaoTree {
+            xs,
+            lines: vec![None; 4 * n],
+            is_max,
+        }
+    }
+
+    /// Adds the line `y = a * x + b`.
+    pub fn add_line(
+        &mut self,
+        a: i64,
+        b: i64,
+    ) {
+        let line = li_chao_line(a, b, self.is_max);
+        let n = self.xs.len();
+        if n > 0 {
+            self.insert(1, 0, n, line);
+        }
+    }
+
+    /// Adds the segment `y = a * x + b` defined only for `x` in `[l, r)`.
+    pub fn add_segment(
+        &mut self,
+        a: i64,
+        b: i64,
+        l: i64,
+        r: i64,
+    ) {
+        let line = li_chao_line(a, b, self.is_max);
+        let ql = self.xs.partition_point(|&x| x < l);
+        let qr = self.xs.partition_point(|&x| x < r);
+        if ql < qr {
+            self.insert_segment(1, 0, self.xs.len(), ql, qr, line);
+        }
+    }
+
+    fn insert_segment(
+        &mut self,
+        k: usize,
+        l: usize,
+        r: usize,
+        ql: usize,
+        qr: usize,
+        line: (i64, i64),
+    ) {
+        if qr <= l || r <= ql {
+            return;
+        }
+        if ql <= l && r <= qr {
+            self.insert(k, l, r, line);
+            return;
+        }
+        let m = (l + r) / 2;
+        self.insert_segment(2 * k, l, m, ql, qr, line);
+        self.insert_segment(2 * k + 1, m, r, ql, qr, line);
+    }
+
+    // Inserts `line` into the node `k` covering the indices `[l, r)`.
+    fn insert(
+        &mut self,
+        mut k: usize,
+        mut l: usize,
+        mut r: usize,
+        mut line: (i64, i64),
+    ) {
+        loop {
+            let cur = match self.lines[k] {
+                None => {
+                    self.lines[k] = Some(line);
+                    return;
+                }
+                Some(cur) => cur,
+            };
+            let m = (l + r) / 2;
+            let (xl, xm, xr) = (self.xs[l], self.xs[m], self.xs[r - 1]);
+            if li_chao_eval(line, xm) < li_chao_eval(cur, xm) {
+                self.lines[k] = Some(line);
+                line = cur;
+            }
+            if r - l == 1 {
+                return;
+            }
+            // `line` is now worse at xm, so it can win on at most one side
+            let cur = self.lines[k].unwrap();
+            if li_chao_eval(line, xl) < li_chao_eval(cur, xl) {
+                k *= 2;
+                r = m;
+            } else if li_chao_eval(line, xr) < li_chao_eval(cur, xr) {
+                k = 2 * k + 1;
+                l = m;
+            } else {
+                return;
+            }
+        }
+    }
+
+    /// Returns the minimum (or maximum) value at `x`, or `None` if no line covers `x`.
+    ///
+    /// # Panics
+    /// Panics if `x` is not one of the points given at construction, or if the answer overflows i64.
+    pub fn query(
+        &self,
+        x: i64,
+    ) -> Option<i64> {
+        let i = self.xs.binary_search(&x).expect("x must be one of the given points");
+        let (mut k, mut l, mut r) = (1, 0, self.xs.len());
+        let mut res: Option<i128> = None;
+        loop {
+            if let Some(line) = self.lines[k] {
+                let y = li_chao_eval(line, x);
+                res = Some(res.map_or(y, |v| v.min(y)));
+            }
+            if r - l == 1 {
+                break;
+            }
+            let m = (l + r) / 2;
+            if i < m {
+                k *= 2;
+                r = m;
+            } else {
+                k = 2 * k + 1;
+                l = m;
+            }
+        }
+        res.map(|v| li_chao_output(v, self.is_max))
+    }
+}
+
+/// Dynamic Li Chao Tree (Line / Segment Container over an Integer Range)
+///
+/// Same as `LiChaoTree`, but works on every integer `x` in `[lo, hi)` without knowing the query points.
+/// Nodes are created on demand.
+///
+/// # Examples
+///
+/// ```
+/// let mut lct = DynamicLiChaoTree::new_min(-1_000_000_000, 1_000_000_000);
+/// lct.add_line(2, 3);
+/// lct.add_segment(-1, 0, 10, 20);
+/// assert_eq!(lct.query(-5), Some(-7));
+/// assert_eq!(lct.query(15), Some(-15));
+/// assert_eq!(lct.query(25), Some(53));
+/// ```
+///
+/// # Complexity
+/// - `add_line`, `query`: O(log (hi - lo))
+/// - `add_segment`: O(log^2 (hi - lo))
+#[derive(Debug, Clone)]
+pub struct DynamicLiChaoTree {
+    lo: i64,
+    hi: i64,
+    nodes: Vec<LiChaoNode>,
+    is_max: bool,
+}
+
+#[derive(Debug, Clone)]
+struct LiChaoNode {
+    line: Option<(i64, i64)>,
+    children: [usize; 2],
+}
+
+impl DynamicLiChaoTree {
+    const NULL: usize = usize::MAX;
+
+    /// Creates a tree that answers minimum queries for `x` in `[lo, hi)`.
+    pub fn new_min(
+        lo: i64,
+        hi: i64,
+    ) -> Self {
+        Self::new(lo, hi, false)
+    }
+
+    /// Creates a tree that answers maximum queries for `x` in `[lo, hi)`.
+    pub fn new_max(
+        lo: i64,
+        hi: i64,
+    ) -> Self {
+        Self::new(lo, hi, true)
+    }
+
+    fn new(
+        lo: i64,
+        hi: i64,
+        is_max: bool,
+    ) -> Self {
+        assert!(lo < hi, "Invalid range");
+        DynamicLiChaoTree {
+            lo,
+            hi,
+            nodes: Vec::new(),
+            is_max,
+        }
+    }
+
+    fn new_node(
+        &mut self,
+        line: Option<(i64, i64)>,
+    ) -> usize {
+        self.nodes.push(LiChaoNode {
+            line,
+            children: [Self::NULL; 2],
+        });
+        self.nodes.len() - 1
+    }
+
+    /// Adds the line `y = a * x + b`.
+    pub fn add_line(
+        &mut self,
+        a: i64,
+        b: i64,
+    ) {
+        self.add_segment(a, b, self.lo, self.hi);
+    }
+
+    /// Adds the segment `y = a * x + b` defined only for `x` in `[l, r)`.
+    pub fn add_segment(
+        &mut self,
+        a: i64,
+        b: i64,
+        l: i64,
+        r: i64,
+    ) {
+        let line = li_chao_line(a, b, self.is_max);
+        let (l, r) = (l.max(self.lo), r.min(self.hi));
+        if l >= r {
+            return;
+        }
+        if self.nodes.is_empty() {
+            self.new_node(None);
+        }
+        self.insert_segment(0, self.lo, self.hi, l, r, line);
+    }
+
+    fn insert_segment(
+        &mut self,
+        k: usize,
+        l: i64,
+        r: i64,
+        ql: i64,
+        qr: i64,
+        line: (i64, i64),
+    ) {
+        if ql <= l && r <= qr {
+            self.insert(k, l, r, line);
+            return;
+        }
+        let m = l + (r - l) / 2;
+        for (c, cl, cr) in [(0, l, m), (1, m, r)] {
+            if qr <= cl || cr <= ql {
+                continue;
+            }
+            let mut child = self.nodes[k].children[c];
+            if child == Self::NULL {
+                child = self.new_node(None);
+                self.nodes[k].children[c] = child;
+            }
+            self.insert_segment(child, cl, cr, ql, qr, line);
+        }
+    }
+
+    // Inserts `line` into the node `k` covering `[l, r)`.
+    fn insert(
+        &mut self,
+        mut k: usize,
+        mut l: i64,
+        mut r: i64,
+        mut line: (i64, i64),
+    ) {
+        loop {
+            let cur = match self.nodes[k].line {
+                None => {
+                    self.nodes[k].line = Some(line);
+                    return;
+                }
+                Some(cur) => cur,
+            };
+            let m = l + (r - l) / 2;
+            if li_chao_eval(line, m) < li_chao_eval(cur, m) {
+                self.nodes[k].line = Some(line);
+                line = cur;
+            }
+            if r - l == 1 {
+                return;
+            }
+            let cur = self.nodes[k].line.unwrap();
+            let c = if li_chao_eval(line, l) < li_chao_eval(cur, l) {
+                r = m;
+                0
+            } else if li_chao_eval(line, r - 1) < li_chao_eval(cur, r - 1) {
+                l = m;
+                1
+            } else {
+                return;
+            };
+            let child = self.nodes[k].children[c];
+            if child == Self::NULL {
+                let child = self.new_node(Some(line));
+                self.nodes[k].children[c] = child;
+                return;
+            }
+            k = child;
+        }
+    }
+
+    /// Returns the minimum (or maximum) value at `x`, or `None` if no line covers `x`.
+    ///
+    /// # Panics
+    /// Panics if `x` is out of `[lo, hi)`, or if the answer overflows i64.
+    pub fn query(
+        &self,
+        x: i64,
+    ) -> Option<i64> {
+        assert!(self.lo <= x && x < self.hi, "x is out of range");
+        let (mut k, mut l, mut r) = (0, self.lo, self.hi);
+        let mut res: Option<i128> = None;
+        if self.nodes.is_empty() {
+            return None;
+        }
+        while k != Self::NULL {
+            if let Some(line) = self.nodes[k].line {
+                let y = li_chao_eval(line, x);
+                res = Some(res.map_or(y, |v| v.min(y)));
+            }
+            let m = l + (r - l) / 2;
+            if x < m {
+                k = self.nodes[k].children[0];
+                r = m;
+            } else {
+                k = self.nodes[k].children[1];
+                l = m;
+            }
+        }
+        res.map(|v| li_chao_output(v, self.is_max))
+    }
+}
+
+// Lines are stored negated in max mode, so that both modes keep the minimum.
+fn li_chao_line(
+    a: i64,
+    b: i64,
+    is_max: bool,
+) -> (i64, i64) {
+    if is_max {
+        assert!(a != i64::MIN && b != i64::MIN, "a and b must not be i64::MIN in max mode");
+        (-a, -b)
+    } else {
+        (a, b)
+    }
+}
+
+fn li_chao_eval(
+    (a, b): (i64, i64),
+    x: i64,
+) -> i128 {
+    a as i128 * x as i128 + b as i128
+}
+
+fn li_chao_output(
+    v: i128,
+    is_max: bool,
+) -> i64 {
+    let v = if is_max { -v } else { v };
+    i64::try_from(v).expect("The answer does not fit in i64")
+}
+
+// --- SNAP END ---
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn brute(
+        lines: &[(i64, i64, i64, i64)],
+        x: i64,
+        is_max: bool,
+    ) -> Option<i64> {
+        let values = lines.iter().filter(|&&(_, _, l, r)| l <= x && x < r).map(|&(a, b, _, _)| a * x + b);
+        if is_max {
+            values.max()
+        } else {
+            values.min()
+        }
+    }
+
+    #[test]
+    fn test_li_chao_tree_random() {
+        let mut seed: u64 = 0x9E37_79B9_7F4A_7C15;
+        let mut next = |m: u64| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed % m
+        };
+        let xs: Vec<i64> = (-30..30).collect();
+        for is_max in [false, true] {
+            let mut lct = if is_max { LiChaoTree::new_max(&xs) } else { LiChaoTree::new_min(&xs) };
+            let mut dyn_lct = if is_max {
+                DynamicLiChaoTree::new_max(-30, 30)
+            } else {
+                DynamicLiChaoTree::new_min(-30, 30)
+            };
+            let mut lines = Vec::new();
+            for _ in 0..300 {
+                let a = next(21) as i64 - 10;
+                let b = next(201) as i64 - 100;
+                if next(2) == 0 {
+                    lct.add_line(a, b);
+                    dyn_lct.add_line(a, b);
+                    lines.push((a, b, i64::MIN, i64::MAX));
+                } else {
+                    let l = next(70) as i64 - 35;
+                    let r = next(70) as i64 - 35;
+                    lct.add_segment(a, b, l, r);
+                    dyn_lct.add_segment(a, b, l, r);
+                    lines.push((a, b, l, r));
+                }
+                for &x in &xs {
+                    let expected = brute(&lines, x, is_max);
+                    assert_eq!(lct.query(x), expected);
+                    assert_eq!(dyn_lct.query(x), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_li_chao_tree_large_values() {
+        // a * x overflows i64, but the answers fit
+        let (a, b, x) = (10_000_000_000, -9_000_000_000_000_000_000, 1_000_000_000);
+        let mut lct = LiChaoTree::new_min(&[-x, x]);
+        let mut dyn_lct = DynamicLiChaoTree::new_max(-x, x + 1);
+        for (a, b) in [(a, b), (-a, -b)] {
+            lct.add_line(a, b);
+            dyn_lct.add_line(a, b);
+        }
+        assert_eq!(lct.query(x), Some(-1_000_000_000_000_000_000));
+        assert_eq!(dyn_lct.query(x), Some(1_000_000_000_000_000_000));
+        assert_eq!(dyn_lct.query(x / 2), Some(4_000_000_000_000_000_000));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_li_chao_tree_max_rejects_min_value() {
+        LiChaoTree::new_max(&[0]).add_line(0, i64::MIN);
+    }
+}
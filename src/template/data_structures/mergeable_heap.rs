@@ -0,0 +1,523 @@
+#![allow(dead_code)]
+
+// --- SNAP START ---
+
+/// Leftist Heap (Meldable Min-Heaps in an Arena, with Lazy Add)
+///
+/// Manages many min-heaps at once. A heap is identified by the index of its root node,
+/// and `new_heap()` returns the (node-less) empty heap. Every operation returns the new root.
+///
+/// Unlike `BinaryHeap`, two heaps can be melded in O(log N), which makes small-to-large merging in tree DP
+/// unnecessary. `add` adds a constant to every element of a heap lazily.
+/// For a max-heap, push negated keys.
+///
+/// # Examples
+///
+/// ```
+/// let mut heaps = LeftistHeap::new();
+/// let mut a = heaps.new_heap();
+/// a = heaps.push(a, 5);
+/// a = heaps.push(a, 2);
+/// let mut b = heaps.new_heap();
+/// b = heaps.push(b, 4);
+/// b = heaps.add(b, -3); // {1}
+///
+/// let mut h = heaps.meld(a, b); // {1, 2, 5}
+/// assert_eq!(heaps.top(h), Some(1));
+/// h = heaps.pop(h);
+/// assert_eq!(heaps.top(h), Some(2));
+/// assert_eq!(heaps.len(h), 2);
+/// ```
+///
+/// # Complexity
+/// - `meld`, `push`, `pop`: O(log N)
+/// - `top`, `add`, `len`: O(1)
+#[derive(Debug, Clone)]
+pub struct LeftistHeap<T> {
+    nodes: Vec<LeftistNode<T>>,
+}
+
+#[derive(Debug, Clone)]
+struct LeftistNode<T> {
+    key: T,
+    // Pending addition for the children (the key of this node is already up to date)
+    lazy: T,
+    left: usize,
+    right: usize,
+    // Length of the right spine
+    rank: usize,
+    size: usize,
+}
+
+impl<T> Default for LeftistHeap<T>
+where
+    T: Copy + Ord + std::ops::Add<Output = T> + Default,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> LeftistHeap<T>
+where
+    T: Copy + Ord + std::ops::Add<Output = T> + Default,
+{
+    const EMPTY: usize = usize::MAX;
+
+    pub fn new() -> Self {
+        LeftistHeap { nodes: Vec::new() }
+    }
+
+    /// Returns the root of an empty heap.
+    pub fn new_heap(&self) -> usize {
+        Self::EMPTY
+    }
+
+    fn rank(
+        &self,
+        v: usize,
+    ) -> usize {
+        if v == Self::EMPTY { 0 } else { self.nodes[v].rank }
+    }
+
+    fn apply(
+        &mut self,
+        v: usize,
+        x: T,
+    ) {
+        if v != Self::EMPTY {
+            let node = &mut self.nodes[v];
+            node.key = node.key + x;
+            node.lazy = node.lazy + x;
+        }
+    }
+
+    fn push_down(
+        &mut self,
+        v: usize,
+    ) {
+        let lazy = self.nodes[v].lazy;
+        if lazy != T::default() {
+            let (l, r) = (self.nodes[v].left, self.nodes[v].right);
+            self.apply(l, lazy);
+            self.apply(r, lazy);
+            self.nodes[v].lazy = T::default();
+        }
+    }
+
+    /// Melds two heaps and returns the root of the result. Both inputs are consumed.
+    pub fn meld(
+        &mut self,
+        a: usize,
+        b: usize,
+    ) -> usize {
+        if a == Self::EMPTY {
+            return b;
+        }
+        if b == Self::EMPTY {
+            return a;
+        }
+        let (a, b) = if self.nodes[b].key < self.nodes[a].key { (b, a) } else { (a, b) };
+        self.push_down(a);
+        let right = self.meld(self.nodes[a].right, b);
+        self.nodes[a].right = right;
+        if self.rank(self.nodes[a].left) < self.rank(right) {
+            let node = &mut self.nodes[a];
+            std::mem::swap(&mut node.left, &mut node.right);
+        }
+        let (l, r) = (self.nodes[a].left, self.nodes[a].right);
+        self.nodes[a].rank = self.rank(r) + 1;
+        self.nodes[a].size = self.len(l) + self.len(r) + 1;
+        a
+    }
+
+    /// Pushes `x` into the heap `root` and returns the new root.
+    pub fn push(
+        &mut self,
+        root: usize,
+        x: T,
+    ) -> usize {
+        self.nodes.push(LeftistNode {
+            key: x,
+            lazy: T::default(),
+            left: Self::EMPTY,
+            right: Self::EMPTY,
+            rank: 1,
+            size: 1,
+        });
+        let v = self.nodes.len() - 1;
+        self.meld(root, v)
+    }
+
+    /// Returns the minimum element of the heap `root`.
+    pub fn top(
+        &self,
+        root: usize,
+    ) -> Option<T> {
+        (root != Self::EMPTY).then(|| self.nodes[root].key)
+    }
+
+    /// Removes the minimum element of the heap `root` and returns the new root.
+    ///
+    /// # Panics
+    /// Panics if the heap is empty.
+    pub fn pop(
+        &mut self,
+        root: usize,
+    ) -> usize {
+        assert!(root != Self::EMPTY, "The heap is empty");
+        self.push_down(root);
+        let (l, r) = (self.nodes[root].left, self.nodes[root].right);
+        self.meld(l, r)
+    }
+
+    /// Adds `x` to every element of the heap `root` and returns the root (unchanged).
+    pub fn add(
+        &mut self,
+        root: usize,
+        x: T,
+    ) -> usize {
+        self.apply(root, x);
+        root
+    }
+
+    /// Returns the number of elements in the heap `root`.
+    pub fn len(
+        &self,
+        root: usize,
+    ) -> usize {
+        if root == Self::EMPTY { 0 } else { self.nodes[root].size }
+    }
+}
+
+/// Pairing Heap (Min-Heap with decrease_key)
+///
+/// `push` returns a handle to the inserted element, whose key can later be lowered with `decrease_key`.
+/// This lets Dijkstra's algorithm keep one entry per vertex instead of pushing duplicates:
+/// store the handle of each vertex and call `decrease_key` on relaxation.
+/// `WeightedGraph::dijkstra_pairing` in `graph/weighted.rs` does exactly this.
+///
+/// # Examples
+///
+/// ```
+/// let mut pq = PairingHeap::new();
+/// let a = pq.push(10, 'a');
+/// let _b = pq.push(7, 'b');
+/// pq.decrease_key(a, 3);
+/// assert_eq!(pq.pop(), Some((3, 'a')));
+/// assert_eq!(pq.pop(), Some((7, 'b')));
+/// assert_eq!(pq.pop(), None);
+/// ```
+///
+/// Dijkstra with one entry per vertex:
+///
+/// ```
+/// let adj: Vec<Vec<(usize, i64)>> = vec![vec![(1, 4), (2, 1)], vec![], vec![(1, 2)]];
+/// let mut dist = vec![i64::MAX; 3];
+/// let mut handle = vec![None; 3];
+/// let mut pq = PairingHeap::new();
+/// dist[0] = 0;
+/// handle[0] = Some(pq.push(0, 0));
+/// while let Some((d, u)) = pq.pop() {
+///     for &(v, w) in &adj[u] {
+///         if d + w < dist[v] {
+///             dist[v] = d + w;
+///             match handle[v] {
+///                 Some(h) => pq.decrease_key(h, dist[v]),
+///                 None => handle[v] = Some(pq.push(dist[v], v)),
+///             }
+///         }
+///     }
+/// }
+/// assert_eq!(dist, vec![0, 3, 1]);
+/// ```
+///
+/// # Complexity
+/// - `push`, `top`: O(1)
+/// - `pop`: amortized O(log N)
+/// - `decrease_key`: amortized o(log N)
+#[derive(Debug, Clone)]
+pub struct PairingHeap<K, V> {
+    nodes: Vec<PairingNode<K, V>>,
+    root: usize,
+    len: usize,
+}
+
+#[derive(Debug, Clone)]
+struct PairingNode<K, V> {
+    key: K,
+    value: V,
+    child: usize,
+    next: usize,
+    // Parent if this node is the first child, otherwise the previous sibling
+    prev: usize,
+    in_heap: bool,
+}
+
+impl<K: Copy + Ord, V: Clone> Default for PairingHeap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Copy + Ord, V: Clone> PairingHeap<K, V> {
+    const NIL: usize = usize::MAX;
+
+    pub fn new() -> Self {
+        PairingHeap {
+            nodes: Vec::new(),
+            root: Self::NIL,
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // Links two roots and returns the new root.
+    fn link(
+        &mut self,
+        a: usize,
+        b: usize,
+    ) -> usize {
+        if a == Self::NIL {
+            return b;
+        }
+        if b == Self::NIL {
+            return a;
+        }
+        let (a, b) = if self.nodes[b].key < self.nodes[a].key { (b, a) } else { (a, b) };
+        let c = self.nodes[a].child;
+        self.nodes[b].next = c;
+        if c != Self::NIL {
+            self.nodes[c].prev = b;
+        }
+        self.nodes[b].prev = a;
+        self.nodes[a].child = b;
+        a
+    }
+
+    /// Pushes `value` with priority `key` and returns its handle.
+    pub fn push(
+        &mut self,
+        key: K,
+        value: V,
+    ) -> usize {
+        self.nodes.push(PairingNode {
+            key,
+            value,
+            child: Self::NIL,
+            next: Self::NIL,
+            prev: Self::NIL,
+            in_heap: true,
+        });
+        let v = self.nodes.len() - 1;
+        self.root = self.link(self.root, v);
+        self.len += 1;
+        v
+    }
+
+    /// Returns the minimum `(key, value)`.
+    pub fn top(&self) -> Option<(K, &V)> {
+        (self.root != Self::NIL).then(|| (self.nodes[self.root].key, &self.nodes[self.root].value))
+    }
+
+    /// Removes and returns the minimum `(key, value)`.
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        if self.root == Self::NIL {
+            return None;
+        }
+        let r = self.root;
+        self.nodes[r].in_heap = false;
+        self.len -= 1;
+
+        // Two-pass merge: pair up children from left to right, then link from right to left
+        let mut pairs = Vec::new();
+        let mut c = self.nodes[r].child;
+        while c != Self::NIL {
+            let d = self.nodes[c].next;
+            let next = if d == Self::NIL { Self::NIL } else { self.nodes[d].next };
+            self.nodes[c].next = Self::NIL;
+            self.nodes[c].prev = Self::NIL;
+            if d != Self::NIL {
+                self.nodes[d].next = Self::NIL;
+                self.nodes[d].prev = Self::NIL;
+            }
+            pairs.push(self.link(c, d));
+            c = next;
+        }
+        let mut root = Self::NIL;
+        while let Some(p) = pairs.pop() {
+            root = self.link(p, root);
+        }
+        self.root = root;
+        self.nodes[r].child = Self::NIL;
+        Some((self.nodes[r].key, self.nodes[r].value.clone()))
+    }
+
+    /// Returns whether the element of `handle` is still in the heap.
+    pub fn contains(
+        &self,
+        handle: usize,
+    ) -> bool {
+        self.nodes[handle].in_heap
+    }
+
+    /// Returns the current key of the element of `handle`.
+    pub fn key(
+        &self,
+        handle: usize,
+    ) -> K {
+        self.nodes[handle].key
+    }
+
+    /// Lowers the key of the element of `handle` to `key`.
+    ///
+    /// # Panics
+    /// Panics if the element was already popped or `key` is greater than the current key.
+    pub fn decrease_key(
+        &mut self,
+        handle: usize,
+        key: K,
+    ) {
+        assert!(self.nodes[handle].in_heap, "The element is not in the heap");
+        assert!(key <= self.nodes[handle].key, "The new key must not be greater");
+        self.nodes[handle].key = key;
+        if handle == self.root {
+            return;
+        }
+        // Cut the subtree of `handle` and link it with the root
+        let (prev, next) = (self.nodes[handle].prev, self.nodes[handle].next);
+        if self.nodes[prev].child == handle {
+            self.nodes[prev].child = next;
+        } else {
+            self.nodes[prev].next = next;
+        }
+        if next != Self::NIL {
+            self.nodes[next].prev = prev;
+        }
+        self.nodes[handle].prev = Self::NIL;
+        self.nodes[handle].next = Self::NIL;
+        self.root = self.link(self.root, handle);
+    }
+}
+
+// --- SNAP END ---
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cmp::Reverse;
+    use std::collections::BinaryHeap;
+
+    #[test]
+    fn test_leftist_heap_random() {
+        let mut seed: u64 = 0x5851_F42D_4C95_7F2D;
+        let mut next = |m: u64| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed % m
+        };
+        let mut heaps = LeftistHeap::<i64>::new();
+        let mut roots = [heaps.new_heap(); 8];
+        let mut naive: Vec<Vec<i64>> = vec![Vec::new(); 8];
+        for _ in 0..5000 {
+            let i = next(8) as usize;
+            match next(5) {
+                0 | 1 => {
+                    let x = next(1000) as i64 - 500;
+                    roots[i] = heaps.push(roots[i], x);
+                    naive[i].push(x);
+                }
+                2 => {
+                    let j = next(8) as usize;
+                    if i != j {
+                        roots[i] = heaps.meld(roots[i], roots[j]);
+                        roots[j] = heaps.new_heap();
+                        let moved = std::mem::take(&mut naive[j]);
+                        naive[i].extend(moved);
+                    }
+                }
+                3 => {
+                    let x = next(21) as i64 - 10;
+                    roots[i] = heaps.add(roots[i], x);
+                    naive[i].iter_mut().for_each(|y| *y += x);
+                }
+                _ => {
+                    if !naive[i].is_empty() {
+                        roots[i] = heaps.pop(roots[i]);
+                        let pos = (0..naive[i].len()).min_by_key(|&k| naive[i][k]).unwrap();
+                        naive[i].swap_remove(pos);
+                    }
+                }
+            }
+            for k in 0..8 {
+                assert_eq!(heaps.top(roots[k]), naive[k].iter().copied().min());
+                assert_eq!(heaps.len(roots[k]), naive[k].len());
+            }
+        }
+    }
+
+    #[test]
+    fn test_pairing_heap_dijkstra() {
+        let mut seed: u64 = 0x6A09_E667_F3BC_C908;
+        let mut next = |m: u64| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed % m
+        };
+        let n = 200;
+        let mut adj = vec![vec![]; n];
+        for _ in 0..1000 {
+            let u = next(n as u64) as usize;
+            let v = next(n as u64) as usize;
+            adj[u].push((v, next(100) as i64));
+        }
+
+        let mut expected = vec![i64::MAX; n];
+        let mut bh = BinaryHeap::new();
+        expected[0] = 0;
+        bh.push(Reverse((0, 0)));
+        while let Some(Reverse((d, u))) = bh.pop() {
+            if d > expected[u] {
+                continue;
+            }
+            for &(v, w) in &adj[u] {
+                if d + w < expected[v] {
+                    expected[v] = d + w;
+                    bh.push(Reverse((d + w, v)));
+                }
+            }
+        }
+
+        let mut dist = vec![i64::MAX; n];
+        let mut handle = vec![None; n];
+        let mut pq = PairingHeap::new();
+        dist[0] = 0;
+        handle[0] = Some(pq.push(0, 0));
+        while let Some((d, u)) = pq.pop() {
+            assert_eq!(d, dist[u]);
+            for &(v, w) in &adj[u] {
+                if d + w < dist[v] {
+                    dist[v] = d + w;
+                    match handle[v] {
+                        Some(h) => {
+                            assert!(pq.contains(h));
+                            pq.decrease_key(h, dist[v]);
+                        }
+                        None => handle[v] = Some(pq.push(dist[v], v)),
+                    }
+                }
+            }
+        }
+        assert_eq!(dist, expected);
+        assert!(pq.is_empty());
+    }
+}
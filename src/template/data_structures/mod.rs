@@ -1,4 +1,23 @@
 mod union_find;
 mod segment_tree;
 mod lazy_segment_tree;
-mod dynamic_segment_tree;
\ No newline at end of file
+mod dynamic_segment_tree;
+mod fenwick_tree;
+mod rollback_union_find;
+mod offline_dynamic_connectivity;
+mod monoid;
+mod dynamic_lazy_segment_tree;
+mod persistent_segment_tree;
+mod segment_tree_beats;
+mod sparse_table;
+mod implicit_treap;
+mod sorted_multiset;
+mod wavelet_matrix;
+mod binary_trie;
+mod li_chao_tree;
+mod convex_hull_trick;
+pub(super) mod mergeable_heap;
+mod segment_tree_monoid;
+mod lazy_segment_tree_monoid;
+mod sparse_table_monoid;
+mod implicit_treap_monoid;
\ No newline at end of file
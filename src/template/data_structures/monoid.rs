@@ -0,0 +1,311 @@
+#![allow(dead_code)]
+
+// --- SNAP START ---
+
+use std::marker::PhantomData;
+use std::ops::{Add, BitXor, Mul, Rem};
+
+/// Trait for Monoid, shared by `SegmentTree`, `LazySegmentTree` and `DynamicSegTree`.
+///
+/// A monoid is a set with an associative binary operation and an identity element.
+pub trait Monoid {
+    /// The type of the elements in the monoid.
+    type S: Clone;
+    /// The identity element of the monoid.
+    fn identity() -> Self::S;
+    /// The associative binary operation.
+    fn binary_operation(a: &Self::S, b: &Self::S) -> Self::S;
+}
+
+/// Trait for a monoid acted on by a monoid of maps, used in `LazySegmentTree`.
+///
+/// Requirements:
+/// - `mapping(f, binary_operation(a, b)) == binary_operation(mapping(f, a), mapping(f, b))`
+/// - `mapping(composition(f, g), x) == mapping(f, mapping(g, x))`
+pub trait MapMonoid {
+    /// The monoid of the data.
+    type M: Monoid;
+    /// The type of the maps.
+    type F: Clone;
+    /// The identity map.
+    fn identity_map() -> Self::F;
+    /// Applies the map `f` to `x`.
+    fn mapping(f: &Self::F, x: &<Self::M as Monoid>::S) -> <Self::M as Monoid>::S;
+    /// Returns `f ∘ g` (apply `g` first, then `f`).
+    fn composition(f: &Self::F, g: &Self::F) -> Self::F;
+
+    fn identity_element() -> <Self::M as Monoid>::S {
+        Self::M::identity()
+    }
+    fn binary_operation(a: &<Self::M as Monoid>::S, b: &<Self::M as Monoid>::S) -> <Self::M as Monoid>::S {
+        Self::M::binary_operation(a, b)
+    }
+}
+
+/// Minimum and maximum values of integer types, used as identities of `Max` / `Min`.
+pub trait BoundedValue {
+    fn min_value() -> Self;
+    fn max_value() -> Self;
+}
+
+macro_rules! impl_bounded_value {
+    ($($t:ty),*) => {
+        $(
+            impl BoundedValue for $t {
+                fn min_value() -> Self { <$t>::MIN }
+                fn max_value() -> Self { <$t>::MAX }
+            }
+        )*
+    };
+}
+impl_bounded_value!(i32, i64, i128, isize, u32, u64, u128, usize);
+
+// ====================================================
+// Monoids
+// ====================================================
+
+/// Range Sum (identity: `T::default()`)
+pub struct Sum<T>(PhantomData<T>);
+
+impl<T: Clone + Default + Add<Output = T>> Monoid for Sum<T> {
+    type S = T;
+    fn identity() -> T {
+        T::default()
+    }
+    fn binary_operation(a: &T, b: &T) -> T {
+        a.clone() + b.clone()
+    }
+}
+
+/// Range Minimum (identity: `T::MAX`)
+pub struct Min<T>(PhantomData<T>);
+
+impl<T: Copy + Ord + BoundedValue> Monoid for Min<T> {
+    type S = T;
+    fn identity() -> T {
+        T::max_value()
+    }
+    fn binary_operation(a: &T, b: &T) -> T {
+        *a.min(b)
+    }
+}
+
+/// Range Maximum (identity: `T::MIN`)
+pub struct Max<T>(PhantomData<T>);
+
+impl<T: Copy + Ord + BoundedValue> Monoid for Max<T> {
+    type S = T;
+    fn identity() -> T {
+        T::min_value()
+    }
+    fn binary_operation(a: &T, b: &T) -> T {
+        *a.max(b)
+    }
+}
+
+/// Range GCD over non-negative integers (identity: `0`)
+pub struct Gcd<T>(PhantomData<T>);
+
+impl<T: Copy + Default + PartialEq + Rem<Output = T>> Monoid for Gcd<T> {
+    type S = T;
+    fn identity() -> T {
+        T::default()
+    }
+    fn binary_operation(a: &T, b: &T) -> T {
+        let (mut a, mut b) = (*a, *b);
+        while b != T::default() {
+            let r = a % b;
+            a = b;
+            b = r;
+        }
+        a
+    }
+}
+
+/// Range XOR (identity: `0`)
+pub struct BitwiseXor<T>(PhantomData<T>);
+
+impl<T: Copy + Default + BitXor<Output = T>> Monoid for BitwiseXor<T> {
+    type S = T;
+    fn identity() -> T {
+        T::default()
+    }
+    fn binary_operation(a: &T, b: &T) -> T {
+        *a ^ *b
+    }
+}
+
+/// Composition of affine functions `(a, b)`: `x -> a * x + b`.
+///
+/// `binary_operation(f, g)` applies `f` first, then `g`, so a range product
+/// `f_l, f_{l+1}, ..., f_{r-1}` is the function applying them from left to right.
+pub struct Affine<T>(PhantomData<T>);
+
+impl<T: Copy + Add<Output = T> + Mul<Output = T> + From<u32>> Monoid for Affine<T> {
+    type S = (T, T);
+    fn identity() -> (T, T) {
+        (T::from(1), T::from(0))
+    }
+    fn binary_operation(f: &(T, T), g: &(T, T)) -> (T, T) {
+        (g.0 * f.0, g.0 * f.1 + g.1)
+    }
+}
+
+// ====================================================
+// Map Monoids
+// ====================================================
+
+/// Range Add + Range Minimum
+///
+/// `T::MAX` is reserved as the identity (no element) and is never shifted by an add,
+/// so every element must stay strictly below `T::MAX` (use e.g. `1 << 60` as infinity instead).
+pub struct RangeAddRangeMin<T>(PhantomData<T>);
+
+impl<T: Copy + Ord + BoundedValue + Default + Add<Output = T>> MapMonoid for RangeAddRangeMin<T> {
+    type M = Min<T>;
+    type F = T;
+    fn identity_map() -> T {
+        T::default()
+    }
+    fn mapping(f: &T, x: &T) -> T {
+        // Keep the identity (no element) unchanged; a real element equal to T::MAX is not supported
+        if *x == T::max_value() { *x } else { *x + *f }
+    }
+    fn composition(f: &T, g: &T) -> T {
+        *f + *g
+    }
+}
+
+/// Range Add + Range Maximum
+///
+/// `T::MIN` is reserved as the identity (no element) and is never shifted by an add,
+/// so every element must stay strictly above `T::MIN`.
+pub struct RangeAddRangeMax<T>(PhantomData<T>);
+
+impl<T: Copy + Ord + BoundedValue + Default + Add<Output = T>> MapMonoid for RangeAddRangeMax<T> {
+    type M = Max<T>;
+    type F = T;
+    fn identity_map() -> T {
+        T::default()
+    }
+    fn mapping(f: &T, x: &T) -> T {
+        // Keep the identity (no element) unchanged; a real element equal to T::MIN is not supported
+        if *x == T::min_value() { *x } else { *x + *f }
+    }
+    fn composition(f: &T, g: &T) -> T {
+        *f + *g
+    }
+}
+
+/// Pair of (sum, length), the data type of range-sum map monoids.
+pub struct SumWithLen<T>(PhantomData<T>);
+
+impl<T: Copy + Default + Add<Output = T>> Monoid for SumWithLen<T> {
+    type S = (T, T);
+    fn identity() -> (T, T) {
+        (T::default(), T::default())
+    }
+    fn binary_operation(a: &(T, T), b: &(T, T)) -> (T, T) {
+        (a.0 + b.0, a.1 + b.1)
+    }
+}
+
+/// Range Add + Range Sum
+///
+/// Data is `(sum, len)`; initialize each leaf with `(value, 1)`.
+pub struct RangeAddRangeSum<T>(PhantomData<T>);
+
+impl<T: Copy + Default + Add<Output = T> + Mul<Output = T>> MapMonoid for RangeAddRangeSum<T> {
+    type M = SumWithLen<T>;
+    type F = T;
+    fn identity_map() -> T {
+        T::default()
+    }
+    fn mapping(f: &T, x: &(T, T)) -> (T, T) {
+        (x.0 + *f * x.1, x.1)
+    }
+    fn composition(f: &T, g: &T) -> T {
+        *f + *g
+    }
+}
+
+/// Range Affine (`x -> a * x + b`) + Range Sum
+///
+/// Data is `(sum, len)`; initialize each leaf with `(value, 1)`.
+pub struct RangeAffineRangeSum<T>(PhantomData<T>);
+
+impl<T: Copy + Default + Add<Output = T> + Mul<Output = T> + From<u32>> MapMonoid for RangeAffineRangeSum<T> {
+    type M = SumWithLen<T>;
+    type F = (T, T);
+    fn identity_map() -> (T, T) {
+        (T::from(1), T::from(0))
+    }
+    fn mapping(f: &(T, T), x: &(T, T)) -> (T, T) {
+        (f.0 * x.0 + f.1 * x.1, x.1)
+    }
+    fn composition(f: &(T, T), g: &(T, T)) -> (T, T) {
+        (f.0 * g.0, f.0 * g.1 + f.1)
+    }
+}
+
+// --- SNAP END ---
+
+#[cfg(test)]
+mod tests {
+    use super::super::dynamic_segment_tree::DynamicSegTree;
+    use super::super::lazy_segment_tree::LazySegmentTree;
+    use super::super::segment_tree::SegmentTree;
+    use super::*;
+
+    #[test]
+    fn test_segment_tree_from_monoid() {
+        let data = vec![12u64, 18, 7, 30, 24];
+        let st = SegmentTree::from_monoid::<Gcd<u64>>(&data);
+        assert_eq!(st.query(0, 2), 6);
+        assert_eq!(st.query(3, 5), 6);
+        assert_eq!(st.query(0, 5), 1);
+
+        let st = SegmentTree::from_monoid::<Max<i64>>(&[3, -1, 4]);
+        assert_eq!(st.query(0, 3), 4);
+        assert_eq!(st.query(1, 1), i64::MIN);
+
+        let st = SegmentTree::from_monoid::<BitwiseXor<u32>>(&[1, 2, 4, 8]);
+        assert_eq!(st.query(1, 4), 14);
+
+        // f0(x) = 2x + 1, f1(x) = 3x, f2(x) = x + 5
+        let st = SegmentTree::from_monoid::<Affine<i64>>(&[(2, 1), (3, 0), (1, 5)]);
+        let (a, b) = st.query(0, 3);
+        assert_eq!(a * 10 + b, ((10 * 2 + 1) * 3) + 5);
+    }
+
+    #[test]
+    fn test_lazy_segment_tree_from_map_monoid() {
+        let mut st = LazySegmentTree::from_map_monoid::<RangeAddRangeMin<i64>>(&[5, 3, 8, 6, 1]);
+        st.apply_range(0, 3, 10);
+        assert_eq!(st.prod(0, 3), 13);
+        assert_eq!(st.prod(0, 5), 1);
+        st.apply_range(3, 5, -2);
+        assert_eq!(st.prod(2, 5), -1);
+
+        let data: Vec<(i64, i64)> = vec![1, 2, 3, 4].into_iter().map(|x| (x, 1)).collect();
+        let mut st = LazySegmentTree::from_map_monoid::<RangeAffineRangeSum<i64>>(&data);
+        st.apply_range(1, 4, (2, 1)); // [1, 5, 7, 9]
+        st.apply_range(0, 2, (1, -1)); // [0, 4, 7, 9]
+        assert_eq!(st.prod(0, 4).0, 20);
+        assert_eq!(st.prod(1, 3).0, 11);
+
+        let mut st = LazySegmentTree::from_map_monoid::<RangeAddRangeSum<i64>>(&data);
+        st.apply_range(0, 4, 3);
+        assert_eq!(st.all_prod(), (22, 4));
+    }
+
+    #[test]
+    fn test_dynamic_segment_tree_with_monoid() {
+        let mut st = DynamicSegTree::<Min<i64>>::new(0, 1_000_000_000_000_000_000);
+        st.update(999_999_999_999, 7);
+        st.update(5, 3);
+        assert_eq!(st.query(0, 1_000_000_000_000), 3);
+        assert_eq!(st.query(6, 1_000_000_000_000), 7);
+        assert_eq!(st.query(6, 100), i64::MAX);
+    }
+}
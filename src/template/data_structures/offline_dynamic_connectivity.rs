@@ -0,0 +1,295 @@
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+
+// INJECT: src/template/data_structures/rollback_union_find.rs
+
+// --- SNAP START ---
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operation {
+    Connected(usize, usize),
+    ComponentSize(usize),
+    Components,
+    Nop,
+}
+
+/// Answer to a query of `OfflineDynamicConnectivity`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Answer {
+    /// Answer to `query_connected`
+    Connected(bool),
+    /// Answer to `query_component_size`
+    ComponentSize(usize),
+    /// Answer to `query_components`
+    Components(usize),
+}
+
+/// Offline Dynamic Connectivity
+///
+/// Processes a sequence of edge insertions/deletions interleaved with connectivity queries.
+/// Each edge is alive on an interval of time, which is inserted into a segment tree over time.
+/// A DFS over the segment tree unites edges on the way down and rolls them back on the way up.
+///
+/// Parallel edges are allowed; `remove_edge(u, v)` removes one copy of the edge `{u, v}`.
+///
+/// # Complexity
+/// - O(Q log Q log N)
+///
+/// # Usage
+/// ```
+/// let mut dc = OfflineDynamicConnectivity::new(4);
+/// dc.add_edge(0, 1);
+/// dc.add_edge(1, 2);
+/// let q0 = dc.query_connected(0, 2);
+/// dc.remove_edge(0, 1);
+/// let q1 = dc.query_connected(0, 2);
+/// let q2 = dc.query_components();
+///
+/// let ans = dc.solve();
+/// assert_eq!(ans, vec![Answer::Connected(true), Answer::Connected(false), Answer::Components(3)]);
+/// assert_eq!((q0, q1, q2), (0, 1, 2));
+/// ```
+#[derive(Debug, Clone)]
+pub struct OfflineDynamicConnectivity {
+    n: usize,
+    ops: Vec<Operation>,
+    /// Alive intervals `[l, r)` over operation indices for each edge
+    intervals: Vec<(usize, usize, usize, usize)>,
+    /// Start times of the currently alive copies of each edge
+    alive: HashMap<(usize, usize), Vec<usize>>,
+    num_queries: usize,
+}
+
+impl OfflineDynamicConnectivity {
+    pub fn new(n: usize) -> Self {
+        OfflineDynamicConnectivity {
+            n,
+            ops: Vec::new(),
+            intervals: Vec::new(),
+            alive: HashMap::new(),
+            num_queries: 0,
+        }
+    }
+
+    /// Adds an undirected edge `{u, v}`.
+    pub fn add_edge(
+        &mut self,
+        u: usize,
+        v: usize,
+    ) {
+        let key = (u.min(v), u.max(v));
+        self.alive.entry(key).or_default().push(self.ops.len());
+        self.ops.push(Operation::Nop);
+    }
+
+    /// Removes one copy of the undirected edge `{u, v}`.
+    ///
+    /// # Panics
+    /// Panics if the edge does not exist.
+    pub fn remove_edge(
+        &mut self,
+        u: usize,
+        v: usize,
+    ) {
+        let key = (u.min(v), u.max(v));
+        let start = self
+            .alive
+            .get_mut(&key)
+            .and_then(|starts| starts.pop())
+            .expect("Edge does not exist");
+        self.intervals.push((start, self.ops.len(), key.0, key.1));
+        self.ops.push(Operation::Nop);
+    }
+
+    /// Asks whether `u` and `v` are connected at this point. Returns the query index.
+    pub fn query_connected(
+        &mut self,
+        u: usize,
+        v: usize,
+    ) -> usize {
+        self.push_query(Operation::Connected(u, v))
+    }
+
+    /// Asks the size of the component containing `u` at this point. Returns the query index.
+    pub fn query_component_size(
+        &mut self,
+        u: usize,
+    ) -> usize {
+        self.push_query(Operation::ComponentSize(u))
+    }
+
+    /// Asks the number of connected components at this point. Returns the query index.
+    pub fn query_components(&mut self) -> usize {
+        self.push_query(Operation::Components)
+    }
+
+    fn push_query(
+        &mut self,
+        op: Operation,
+    ) -> usize {
+        self.ops.push(op);
+        self.num_queries += 1;
+        self.num_queries - 1
+    }
+
+    /// Answers all queries in the order they were asked.
+    pub fn solve(&self) -> Vec<Answer> {
+        let t = self.ops.len();
+        let mut size = 1;
+        while size < t {
+            size *= 2;
+        }
+
+        // Edges that are still alive at the end live until time t
+        let mut intervals = self.intervals.clone();
+        for (&(u, v), starts) in &self.alive {
+            for &start in starts {
+                intervals.push((start, t, u, v));
+            }
+        }
+
+        let mut seg = vec![Vec::new(); 2 * size];
+        for &(l, r, u, v) in &intervals {
+            let mut l = l + size;
+            let mut r = r + size;
+            while l < r {
+                if l & 1 == 1 {
+                    seg[l].push((u, v));
+                    l += 1;
+                }
+                if r & 1 == 1 {
+                    r -= 1;
+                    seg[r].push((u, v));
+                }
+                l >>= 1;
+                r >>= 1;
+            }
+        }
+
+        let mut uf = RollbackUnionFind::new(self.n);
+        let mut answers = Vec::with_capacity(self.num_queries);
+        if t > 0 {
+            self.dfs(1, 0, size, &seg, &mut uf, &mut answers);
+        }
+        answers
+    }
+
+    fn dfs(
+        &self,
+        k: usize,
+        l: usize,
+        r: usize,
+        seg: &[Vec<(usize, usize)>],
+        uf: &mut RollbackUnionFind,
+        answers: &mut Vec<Answer>,
+    ) {
+        let snap = uf.snapshot();
+        for &(u, v) in &seg[k] {
+            uf.unite(u, v);
+        }
+        if r - l == 1 {
+            match self.ops.get(l) {
+                Some(&Operation::Connected(u, v)) => answers.push(Answer::Connected(uf.same(u, v))),
+                Some(&Operation::ComponentSize(u)) => answers.push(Answer::ComponentSize(uf.size(u))),
+                Some(&Operation::Components) => answers.push(Answer::Components(uf.count_components())),
+                _ => {}
+            }
+        } else if l < self.ops.len() {
+            // Subtrees that only cover padding leaves are skipped
+            let mid = (l + r) / 2;
+            self.dfs(2 * k, l, mid, seg, uf, answers);
+            self.dfs(2 * k + 1, mid, r, seg, uf, answers);
+        }
+        uf.rollback(snap);
+    }
+}
+
+// --- SNAP END ---
+
+#[allow(unused_imports)]
+use super::rollback_union_find::RollbackUnionFind;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offline_dynamic_connectivity() {
+        let mut dc = OfflineDynamicConnectivity::new(5);
+        dc.query_components(); // 5
+        dc.add_edge(0, 1);
+        dc.add_edge(1, 2);
+        dc.add_edge(0, 1); // parallel edge
+        dc.query_connected(0, 2); // true
+        dc.remove_edge(1, 0);
+        dc.query_connected(0, 2); // true (the parallel copy remains)
+        dc.remove_edge(0, 1);
+        dc.query_connected(0, 2); // false
+        dc.query_component_size(2); // 2
+        dc.add_edge(3, 4);
+        dc.add_edge(2, 3);
+        dc.query_component_size(4); // 4
+        dc.query_components(); // 2
+
+        assert_eq!(
+            dc.solve(),
+            vec![
+                Answer::Components(5),
+                Answer::Connected(true),
+                Answer::Connected(true),
+                Answer::Connected(false),
+                Answer::ComponentSize(2),
+                Answer::ComponentSize(4),
+                Answer::Components(2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_offline_dynamic_connectivity_random() {
+        // Compare with a naive BFS on a fixed pseudo-random sequence
+        let n = 6;
+        let mut seed: u64 = 88172645463325252;
+        let mut next = || {
+            seed ^= seed << 7;
+            seed ^= seed >> 9;
+            seed
+        };
+        let mut dc = OfflineDynamicConnectivity::new(n);
+        let mut edges: Vec<(usize, usize)> = Vec::new();
+        let mut expected = Vec::new();
+        for _ in 0..300 {
+            match next() % 3 {
+                0 => {
+                    let (u, v) = ((next() % n as u64) as usize, (next() % n as u64) as usize);
+                    dc.add_edge(u, v);
+                    edges.push((u, v));
+                }
+                1 if !edges.is_empty() => {
+                    let (u, v) = edges.swap_remove((next() % edges.len() as u64) as usize);
+                    dc.remove_edge(u, v);
+                }
+                _ => {
+                    let (u, v) = ((next() % n as u64) as usize, (next() % n as u64) as usize);
+                    dc.query_connected(u, v);
+                    let mut seen = vec![false; n];
+                    let mut stack = vec![u];
+                    seen[u] = true;
+                    while let Some(x) = stack.pop() {
+                        for &(a, b) in &edges {
+                            for (p, q) in [(a, b), (b, a)] {
+                                if p == x && !seen[q] {
+                                    seen[q] = true;
+                                    stack.push(q);
+                                }
+                            }
+                        }
+                    }
+                    expected.push(Answer::Connected(seen[v]));
+                }
+            }
+        }
+        assert_eq!(dc.solve(), expected);
+    }
+}
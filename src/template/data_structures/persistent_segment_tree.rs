@@ -0,0 +1,337 @@
+#![allow(dead_code)]
+
+// INJECT: src/template/data_structures/monoid.rs
+
+// --- SNAP START ---
+
+/// Persistent Segment Tree (Point Update, Range Query, Path Copying)
+///
+/// Every `update` copies the O(log N) nodes on the path to the leaf and returns the root of a new version.
+/// All old versions stay valid and can be queried at any time.
+///
+/// A version is identified by its root index (`usize`). Node `0` is a shared node representing
+/// a subtree filled with the identity element, so `empty_root()` costs no memory.
+///
+/// # Examples
+///
+/// ```
+/// let mut st = PersistentSegTree::<Sum<i64>>::new(&[1, 2, 3, 4]);
+/// let v0 = st.initial_root();
+/// let v1 = st.update(v0, 1, 10); // [1, 10, 3, 4]
+/// let v2 = st.update(v1, 3, 0); // [1, 10, 3, 0]
+///
+/// assert_eq!(st.query(v0, 0, 4), 10);
+/// assert_eq!(st.query(v1, 0, 4), 18);
+/// assert_eq!(st.query(v2, 0, 4), 14);
+/// assert_eq!(st.get(v0, 1), 2);
+/// ```
+pub struct PersistentSegTree<M: Monoid> {
+    n: usize,
+    nodes: Vec<PersistentNode<M::S>>,
+    initial: usize,
+}
+
+#[derive(Clone)]
+struct PersistentNode<S> {
+    value: S,
+    left: usize,
+    right: usize,
+}
+
+impl<M: Monoid> PersistentSegTree<M> {
+    /// Creates a new PersistentSegTree from the given data.
+    ///
+    /// # Complexity
+    /// - O(N)
+    pub fn new(data: &[M::S]) -> Self {
+        let mut st = Self::with_len(data.len());
+        if !data.is_empty() {
+            st.initial = st.build(data, 0, data.len());
+        }
+        st
+    }
+
+    /// Creates a new PersistentSegTree of length `n` filled with the identity element.
+    ///
+    /// # Complexity
+    /// - O(1)
+    pub fn with_len(n: usize) -> Self {
+        PersistentSegTree {
+            n,
+            nodes: vec![PersistentNode {
+                value: M::identity(),
+                left: 0,
+                right: 0,
+            }],
+            initial: 0,
+        }
+    }
+
+    /// Returns the root of the initial version.
+    pub fn initial_root(&self) -> usize {
+        self.initial
+    }
+
+    /// Returns the root of a version filled with the identity element.
+    pub fn empty_root(&self) -> usize {
+        0
+    }
+
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    fn build(
+        &mut self,
+        data: &[M::S],
+        l: usize,
+        r: usize,
+    ) -> usize {
+        if r - l == 1 {
+            return self.push_node(data[l].clone(), 0, 0);
+        }
+        let mid = (l + r) / 2;
+        let left = self.build(data, l, mid);
+        let right = self.build(data, mid, r);
+        let value = M::binary_operation(&self.nodes[left].value, &self.nodes[right].value);
+        self.push_node(value, left, right)
+    }
+
+    fn push_node(
+        &mut self,
+        value: M::S,
+        left: usize,
+        right: usize,
+    ) -> usize {
+        self.nodes.push(PersistentNode { value, left, right });
+        self.nodes.len() - 1
+    }
+
+    /// Returns the root of a new version in which the element at `p` is `value`.
+    ///
+    /// # Complexity
+    /// - O(log N) time and memory
+    pub fn update(
+        &mut self,
+        root: usize,
+        p: usize,
+        value: M::S,
+    ) -> usize {
+        assert!(p < self.n, "Index out of bounds");
+        self.update_recursive(root, 0, self.n, p, value)
+    }
+
+    fn update_recursive(
+        &mut self,
+        k: usize,
+        l: usize,
+        r: usize,
+        p: usize,
+        value: M::S,
+    ) -> usize {
+        if r - l == 1 {
+            return self.push_node(value, 0, 0);
+        }
+        let mid = (l + r) / 2;
+        let (mut left, mut right) = (self.nodes[k].left, self.nodes[k].right);
+        if p < mid {
+            left = self.update_recursive(left, l, mid, p, value);
+        } else {
+            right = self.update_recursive(right, mid, r, p, value);
+        }
+        let value = M::binary_operation(&self.nodes[left].value, &self.nodes[right].value);
+        self.push_node(value, left, right)
+    }
+
+    /// Queries the product over `[l, r)` in the version `root`.
+    ///
+    /// # Complexity
+    /// - O(log N)
+    pub fn query(
+        &self,
+        root: usize,
+        l: usize,
+        r: usize,
+    ) -> M::S {
+        assert!(l <= r && r <= self.n, "Invalid range");
+        if l == r {
+            return M::identity();
+        }
+        self.query_recursive(root, 0, self.n, l, r)
+    }
+
+    fn query_recursive(
+        &self,
+        k: usize,
+        l: usize,
+        r: usize,
+        ql: usize,
+        qr: usize,
+    ) -> M::S {
+        if qr <= l || r <= ql || k == 0 {
+            return M::identity();
+        }
+        if ql <= l && r <= qr {
+            return self.nodes[k].value.clone();
+        }
+        let mid = (l + r) / 2;
+        let left_val = self.query_recursive(self.nodes[k].left, l, mid, ql, qr);
+        let right_val = self.query_recursive(self.nodes[k].right, mid, r, ql, qr);
+        M::binary_operation(&left_val, &right_val)
+    }
+
+    /// Gets the element at `p` in the version `root`.
+    ///
+    /// # Complexity
+    /// - O(log N)
+    pub fn get(
+        &self,
+        root: usize,
+        p: usize,
+    ) -> M::S {
+        self.query(root, p, p + 1)
+    }
+}
+
+/// Static range k-th smallest queries using a persistent segment tree over prefix counts.
+///
+/// Version `i` stores the counts of the (compressed) values in `a[0..i]`,
+/// so the counts of `a[l..r]` are the difference between versions `r` and `l`.
+///
+/// # Examples
+///
+/// ```
+/// let kth = RangeKthSmallest::new(&[5, 1, 4, 1, 3]);
+/// assert_eq!(kth.kth_smallest(0, 5, 0), 1);
+/// assert_eq!(kth.kth_smallest(0, 5, 2), 3);
+/// assert_eq!(kth.kth_smallest(1, 4, 2), 4); // sorted [1, 1, 4]
+/// assert_eq!(kth.count_less(0, 3, 5), 2); // {1, 4}
+/// ```
+pub struct RangeKthSmallest<T> {
+    xs: Vec<T>,
+    tree: PersistentSegTree<Sum<usize>>,
+    roots: Vec<usize>,
+}
+
+impl<T: Ord + Clone> RangeKthSmallest<T> {
+    /// # Complexity
+    /// - O(N log N)
+    pub fn new(a: &[T]) -> Self {
+        let mut xs = a.to_vec();
+        xs.sort();
+        xs.dedup();
+        let mut tree = PersistentSegTree::<Sum<usize>>::with_len(xs.len());
+        let mut roots = Vec::with_capacity(a.len() + 1);
+        roots.push(tree.empty_root());
+        for x in a {
+            let idx = xs.binary_search(x).unwrap();
+            let root = *roots.last().unwrap();
+            let cnt = tree.get(root, idx);
+            roots.push(tree.update(root, idx, cnt + 1));
+        }
+        RangeKthSmallest { xs, tree, roots }
+    }
+
+    /// Returns the `k`-th (0-indexed) smallest value in `a[l..r]`.
+    ///
+    /// # Panics
+    /// Panics if `k >= r - l`.
+    ///
+    /// # Complexity
+    /// - O(log N)
+    pub fn kth_smallest(
+        &self,
+        l: usize,
+        r: usize,
+        mut k: usize,
+    ) -> T {
+        assert!(l <= r && r < self.roots.len(), "Invalid range");
+        assert!(k < r - l, "k is out of range");
+        let nodes = &self.tree.nodes;
+        let (mut lo, mut hi) = (self.roots[l], self.roots[r]);
+        let (mut nl, mut nr) = (0, self.xs.len());
+        while nr - nl > 1 {
+            let mid = (nl + nr) / 2;
+            let left_cnt = nodes[nodes[hi].left].value - nodes[nodes[lo].left].value;
+            if k < left_cnt {
+                lo = nodes[lo].left;
+                hi = nodes[hi].left;
+                nr = mid;
+            } else {
+                k -= left_cnt;
+                lo = nodes[lo].right;
+                hi = nodes[hi].right;
+                nl = mid;
+            }
+        }
+        self.xs[nl].clone()
+    }
+
+    /// Returns the number of values less than `upper` in `a[l..r]`.
+    ///
+    /// # Complexity
+    /// - O(log N)
+    pub fn count_less(
+        &self,
+        l: usize,
+        r: usize,
+        upper: T,
+    ) -> usize {
+        assert!(l <= r && r < self.roots.len(), "Invalid range");
+        let idx = self.xs.partition_point(|x| *x < upper);
+        self.tree.query(self.roots[r], 0, idx) - self.tree.query(self.roots[l], 0, idx)
+    }
+}
+
+// --- SNAP END ---
+
+#[allow(unused_imports)]
+use super::monoid::{Monoid, Sum};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_persistent_segment_tree() {
+        let data = vec![3i64, 1, 4, 1, 5];
+        let mut st = PersistentSegTree::<Sum<i64>>::new(&data);
+        let mut versions = vec![(st.initial_root(), data.clone())];
+        for (i, (p, x)) in [(0, 10), (4, -2), (2, 7), (0, 0)].into_iter().enumerate() {
+            // Branch from an older version every other time
+            let (root, arr) = versions[i / 2].clone();
+            let mut arr = arr.clone();
+            arr[p] = x;
+            versions.push((st.update(root, p, x), arr));
+        }
+        for (root, arr) in &versions {
+            for l in 0..=5 {
+                for r in l..=5 {
+                    assert_eq!(st.query(*root, l, r), arr[l..r].iter().sum::<i64>());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_range_kth_smallest() {
+        let a = vec![7, 2, 9, 2, 5, 1, 8, 5];
+        let kth = RangeKthSmallest::new(&a);
+        for l in 0..a.len() {
+            for r in l + 1..=a.len() {
+                let mut sorted = a[l..r].to_vec();
+                sorted.sort();
+                for (k, &x) in sorted.iter().enumerate() {
+                    assert_eq!(kth.kth_smallest(l, r, k), x);
+                }
+                for upper in 0..11 {
+                    assert_eq!(kth.count_less(l, r, upper), sorted.iter().filter(|&&x| x < upper).count());
+                }
+            }
+        }
+    }
+}
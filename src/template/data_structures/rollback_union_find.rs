@@ -0,0 +1,332 @@
+#![allow(dead_code)]
+
+// --- SNAP START ---
+
+/// Union-Find with Rollback
+///
+/// Union by size without path compression, so every successful `unite` can be undone.
+/// Used as the base of offline dynamic connectivity (segment tree over time).
+///
+/// # Usage
+/// ```
+/// let mut uf = RollbackUnionFind::new(n);
+///
+/// uf.unite(0, 1);
+/// let snap = uf.snapshot();
+/// uf.unite(1, 2);
+/// assert!(uf.same(0, 2));
+///
+/// // Undo every unite performed after the snapshot
+/// uf.rollback(snap);
+/// assert!(!uf.same(0, 2));
+/// assert!(uf.same(0, 1));
+/// ```
+#[derive(Debug, Clone)]
+pub struct RollbackUnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    components: usize,
+    /// (child root, parent root) for each successful unite
+    history: Vec<(usize, usize)>,
+}
+
+impl RollbackUnionFind {
+    pub fn new(n: usize) -> Self {
+        RollbackUnionFind {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+            components: n,
+            history: Vec::new(),
+        }
+    }
+
+    /// Finds the root of `x`.
+    ///
+    /// # Complexity
+    /// - O(log N)
+    pub fn find(
+        &self,
+        mut x: usize,
+    ) -> usize {
+        while self.parent[x] != x {
+            x = self.parent[x];
+        }
+        x
+    }
+
+    /// Unites the sets containing `x` and `y`.
+    /// Returns `false` (and records nothing) if they were already in the same set.
+    ///
+    /// # Complexity
+    /// - O(log N)
+    pub fn unite(
+        &mut self,
+        x: usize,
+        y: usize,
+    ) -> bool {
+        let mut root_x = self.find(x);
+        let mut root_y = self.find(y);
+        if root_x == root_y {
+            return false;
+        }
+        if self.size[root_x] < self.size[root_y] {
+            std::mem::swap(&mut root_x, &mut root_y);
+        }
+        self.parent[root_y] = root_x;
+        self.size[root_x] += self.size[root_y];
+        self.components -= 1;
+        self.history.push((root_y, root_x));
+        true
+    }
+
+    pub fn same(
+        &self,
+        x: usize,
+        y: usize,
+    ) -> bool {
+        self.find(x) == self.find(y)
+    }
+
+    pub fn size(
+        &self,
+        x: usize,
+    ) -> usize {
+        self.size[self.find(x)]
+    }
+
+    /// Returns the number of connected components.
+    pub fn count_components(&self) -> usize {
+        self.components
+    }
+
+    /// Returns a token representing the current state, to be passed to `rollback`.
+    pub fn snapshot(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Undoes the last successful `unite`. Returns `false` if there is nothing to undo.
+    ///
+    /// # Complexity
+    /// - O(1)
+    pub fn undo(&mut self) -> bool {
+        if let Some((child, root)) = self.history.pop() {
+            self.parent[child] = child;
+            self.size[root] -= self.size[child];
+            self.components += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Restores the state at the time `snapshot()` returned `to`.
+    ///
+    /// # Panics
+    /// Panics if `to` is newer than the current state.
+    ///
+    /// # Complexity
+    /// - O(number of undone unites)
+    pub fn rollback(
+        &mut self,
+        to: usize,
+    ) {
+        assert!(to <= self.history.len(), "Invalid snapshot");
+        while self.history.len() > to {
+            self.undo();
+        }
+    }
+}
+
+/// Partially Persistent Union-Find
+///
+/// Every call to `unite` advances the time by one. Queries can be asked for any past time `t`,
+/// where time `t` is the state right after the first `t` calls to `unite` (time 0 is the initial state).
+///
+/// # Usage
+/// ```
+/// let mut uf = PartiallyPersistentUnionFind::new(n);
+/// uf.unite(0, 1); // time 1
+/// uf.unite(2, 3); // time 2
+/// uf.unite(1, 2); // time 3
+///
+/// assert!(!uf.same_at(0, 3, 2));
+/// assert!(uf.same_at(0, 3, 3));
+/// assert_eq!(uf.size_at(0, 1), 2);
+///
+/// // The first time 0 and 3 become connected
+/// assert_eq!(uf.connected_time(0, 3), Some(3));
+/// ```
+#[derive(Debug, Clone)]
+pub struct PartiallyPersistentUnionFind {
+    parent: Vec<usize>,
+    /// The time at which `x` stopped being a root (usize::MAX while it is a root)
+    time: Vec<usize>,
+    /// (time, size) history of each root
+    size_history: Vec<Vec<(usize, usize)>>,
+    now: usize,
+}
+
+impl PartiallyPersistentUnionFind {
+    pub fn new(n: usize) -> Self {
+        PartiallyPersistentUnionFind {
+            parent: (0..n).collect(),
+            time: vec![usize::MAX; n],
+            size_history: vec![vec![(0, 1)]; n],
+            now: 0,
+        }
+    }
+
+    /// Returns the current time (the number of `unite` calls so far).
+    pub fn now(&self) -> usize {
+        self.now
+    }
+
+    /// Finds the root of `x` at time `t`.
+    ///
+    /// # Complexity
+    /// - O(log N)
+    pub fn find_at(
+        &self,
+        mut x: usize,
+        t: usize,
+    ) -> usize {
+        while self.time[x] <= t {
+            x = self.parent[x];
+        }
+        x
+    }
+
+    /// Unites the sets containing `x` and `y`, advancing the time by one.
+    /// Returns `false` if they were already in the same set (the time still advances).
+    ///
+    /// # Complexity
+    /// - O(log N)
+    pub fn unite(
+        &mut self,
+        x: usize,
+        y: usize,
+    ) -> bool {
+        self.now += 1;
+        let mut root_x = self.find_at(x, self.now);
+        let mut root_y = self.find_at(y, self.now);
+        if root_x == root_y {
+            return false;
+        }
+        let size_x = self.size_history[root_x].last().unwrap().1;
+        let size_y = self.size_history[root_y].last().unwrap().1;
+        if size_x < size_y {
+            std::mem::swap(&mut root_x, &mut root_y);
+        }
+        self.parent[root_y] = root_x;
+        self.time[root_y] = self.now;
+        self.size_history[root_x].push((self.now, size_x + size_y));
+        true
+    }
+
+    /// Checks if `x` and `y` were in the same set at time `t`.
+    ///
+    /// # Complexity
+    /// - O(log N)
+    pub fn same_at(
+        &self,
+        x: usize,
+        y: usize,
+        t: usize,
+    ) -> bool {
+        self.find_at(x, t) == self.find_at(y, t)
+    }
+
+    /// Returns the size of the set containing `x` at time `t`.
+    ///
+    /// # Complexity
+    /// - O(log N)
+    pub fn size_at(
+        &self,
+        x: usize,
+        t: usize,
+    ) -> usize {
+        let root = self.find_at(x, t);
+        let history = &self.size_history[root];
+        let idx = history.partition_point(|&(time, _)| time <= t);
+        history[idx - 1].1
+    }
+
+    /// Returns the first time at which `x` and `y` became connected, or `None` if they are not connected now.
+    ///
+    /// # Complexity
+    /// - O(log^2 N)
+    pub fn connected_time(
+        &self,
+        x: usize,
+        y: usize,
+    ) -> Option<usize> {
+        if !self.same_at(x, y, self.now) {
+            return None;
+        }
+        if self.same_at(x, y, 0) {
+            return Some(0);
+        }
+        let (mut ng, mut ok) = (0, self.now);
+        while ok - ng > 1 {
+            let mid = (ok + ng) / 2;
+            if self.same_at(x, y, mid) {
+                ok = mid;
+            } else {
+                ng = mid;
+            }
+        }
+        Some(ok)
+    }
+}
+
+// --- SNAP END ---
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rollback_union_find() {
+        let mut uf = RollbackUnionFind::new(5);
+        assert!(uf.unite(0, 1));
+        let snap = uf.snapshot();
+        assert!(uf.unite(2, 3));
+        assert!(!uf.unite(3, 2));
+        assert!(uf.unite(1, 3));
+        assert_eq!(uf.size(0), 4);
+        assert_eq!(uf.count_components(), 2);
+
+        uf.rollback(snap);
+        assert!(uf.same(0, 1));
+        assert!(!uf.same(2, 3));
+        assert_eq!(uf.size(1), 2);
+        assert_eq!(uf.count_components(), 4);
+
+        assert!(uf.undo());
+        assert!(!uf.undo());
+        assert_eq!(uf.count_components(), 5);
+    }
+
+    #[test]
+    fn test_partially_persistent_union_find() {
+        let mut uf = PartiallyPersistentUnionFind::new(5);
+        uf.unite(0, 1); // 1
+        uf.unite(2, 3); // 2
+        uf.unite(0, 1); // 3 (no-op)
+        uf.unite(3, 0); // 4
+        assert_eq!(uf.now(), 4);
+
+        assert!(!uf.same_at(0, 1, 0));
+        assert!(uf.same_at(0, 1, 1));
+        assert!(!uf.same_at(1, 2, 3));
+        assert!(uf.same_at(1, 2, 4));
+        assert_eq!(uf.size_at(2, 1), 1);
+        assert_eq!(uf.size_at(2, 3), 2);
+        assert_eq!(uf.size_at(1, 4), 4);
+        assert_eq!(uf.size_at(4, 4), 1);
+
+        assert_eq!(uf.connected_time(1, 2), Some(4));
+        assert_eq!(uf.connected_time(0, 0), Some(0));
+        assert_eq!(uf.connected_time(0, 4), None);
+    }
+}
@@ -155,6 +155,16 @@
 /// );
 /// ```
 ///
+/// ### Using a `Monoid` implementation
+/// - Inject `segment_tree_monoid.rs` for `from_monoid` (opt-in, so that `monoid.rs` is only pulled in when needed).
+/// - Ready-made monoids are in `monoid.rs` (`Sum`, `Min`, `Max`, `Gcd`, `BitwiseXor`, `Affine`, ...).
+/// ```ignore
+/// let st = SegmentTree::from_monoid::<Min<i64>>(&data);
+///
+/// // As a struct field
+/// struct Solver { st: MonoidSegmentTree<Min<i64>> }
+/// ```
+///
 /// ### String Concatenation
 /// - Problem: Concatenate strings.
 /// - Identity: `""` (Empty string)
@@ -427,3 +437,5 @@ where
         0
     }
 }
+
+// --- SNAP END ---
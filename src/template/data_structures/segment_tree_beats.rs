@@ -0,0 +1,417 @@
+#![allow(dead_code)]
+
+// --- SNAP START ---
+
+/// Segment Tree Beats (Range Chmin / Chmax / Add / Assign, Range Sum / Min / Max)
+///
+/// Each node keeps the largest two distinct values (and the count of the largest),
+/// and symmetrically for the minimum. A `chmin(x)` that only affects the maximum of a node
+/// (`max2 < x < max1`) is applied in O(1) there; otherwise the update descends further.
+///
+/// # Complexity
+/// - Amortized O(log^2 N) per operation
+///
+/// # Examples
+///
+/// ```
+/// let mut st = SegmentTreeBeats::new(&[5, 1, 8, 3, 6]);
+/// st.chmin(0, 5, 4); // [4, 1, 4, 3, 4]
+/// assert_eq!(st.sum(0, 5), 16);
+/// st.chmax(1, 4, 3); // [4, 3, 4, 3, 4]
+/// st.add(0, 2, 10); // [14, 13, 4, 3, 4]
+/// assert_eq!(st.max(0, 5), 14);
+/// st.assign(1, 3, 0); // [14, 0, 0, 3, 4]
+/// assert_eq!(st.min(0, 5), 0);
+/// assert_eq!(st.sum(0, 5), 21);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SegmentTreeBeats {
+    n: usize,
+    size: usize,
+    sum: Vec<i64>,
+    max1: Vec<i64>,
+    max2: Vec<i64>,
+    max_cnt: Vec<i64>,
+    min1: Vec<i64>,
+    min2: Vec<i64>,
+    min_cnt: Vec<i64>,
+    len: Vec<i64>,
+    lazy_add: Vec<i64>,
+}
+
+impl SegmentTreeBeats {
+    const NEG_INF: i64 = i64::MIN;
+    const POS_INF: i64 = i64::MAX;
+
+    /// Creates a new SegmentTreeBeats from the given data.
+    ///
+    /// # Complexity
+    /// - O(N)
+    pub fn new(data: &[i64]) -> Self {
+        let n = data.len();
+        let mut size = 1;
+        while size < n {
+            size *= 2;
+        }
+        let mut st = SegmentTreeBeats {
+            n,
+            size,
+            sum: vec![0; 2 * size],
+            max1: vec![Self::NEG_INF; 2 * size],
+            max2: vec![Self::NEG_INF; 2 * size],
+            max_cnt: vec![0; 2 * size],
+            min1: vec![Self::POS_INF; 2 * size],
+            min2: vec![Self::POS_INF; 2 * size],
+            min_cnt: vec![0; 2 * size],
+            len: vec![0; 2 * size],
+            lazy_add: vec![0; 2 * size],
+        };
+        for (i, &x) in data.iter().enumerate() {
+            let k = size + i;
+            st.sum[k] = x;
+            st.max1[k] = x;
+            st.max_cnt[k] = 1;
+            st.min1[k] = x;
+            st.min_cnt[k] = 1;
+            st.len[k] = 1;
+        }
+        for k in (1..size).rev() {
+            st.len[k] = st.len[2 * k] + st.len[2 * k + 1];
+            st.update(k);
+        }
+        st
+    }
+
+    /// Applies `a[i] = min(a[i], x)` for `i` in `[l, r)`.
+    pub fn chmin(
+        &mut self,
+        l: usize,
+        r: usize,
+        x: i64,
+    ) {
+        assert!(l <= r && r <= self.n, "Invalid range");
+        self.chmin_recursive(1, 0, self.size, l, r, x);
+    }
+
+    /// Applies `a[i] = max(a[i], x)` for `i` in `[l, r)`.
+    pub fn chmax(
+        &mut self,
+        l: usize,
+        r: usize,
+        x: i64,
+    ) {
+        assert!(l <= r && r <= self.n, "Invalid range");
+        self.chmax_recursive(1, 0, self.size, l, r, x);
+    }
+
+    /// Applies `a[i] += x` for `i` in `[l, r)`.
+    pub fn add(
+        &mut self,
+        l: usize,
+        r: usize,
+        x: i64,
+    ) {
+        assert!(l <= r && r <= self.n, "Invalid range");
+        self.add_recursive(1, 0, self.size, l, r, x);
+    }
+
+    /// Applies `a[i] = x` for `i` in `[l, r)`.
+    pub fn assign(
+        &mut self,
+        l: usize,
+        r: usize,
+        x: i64,
+    ) {
+        self.chmin(l, r, x);
+        self.chmax(l, r, x);
+    }
+
+    /// Returns the sum of `[l, r)`.
+    pub fn sum(
+        &mut self,
+        l: usize,
+        r: usize,
+    ) -> i64 {
+        assert!(l <= r && r <= self.n, "Invalid range");
+        self.query_recursive(1, 0, self.size, l, r).0
+    }
+
+    /// Returns the minimum of `[l, r)` (`i64::MAX` if the range is empty).
+    pub fn min(
+        &mut self,
+        l: usize,
+        r: usize,
+    ) -> i64 {
+        assert!(l <= r && r <= self.n, "Invalid range");
+        self.query_recursive(1, 0, self.size, l, r).1
+    }
+
+    /// Returns the maximum of `[l, r)` (`i64::MIN` if the range is empty).
+    pub fn max(
+        &mut self,
+        l: usize,
+        r: usize,
+    ) -> i64 {
+        assert!(l <= r && r <= self.n, "Invalid range");
+        self.query_recursive(1, 0, self.size, l, r).2
+    }
+
+    /// Gets the current value at index `p`.
+    pub fn get(
+        &mut self,
+        p: usize,
+    ) -> i64 {
+        self.sum(p, p + 1)
+    }
+
+    fn update(
+        &mut self,
+        k: usize,
+    ) {
+        let (a, b) = (2 * k, 2 * k + 1);
+        self.sum[k] = self.sum[a] + self.sum[b];
+
+        if self.max1[a] == self.max1[b] {
+            self.max1[k] = self.max1[a];
+            self.max2[k] = self.max2[a].max(self.max2[b]);
+            self.max_cnt[k] = self.max_cnt[a] + self.max_cnt[b];
+        } else if self.max1[a] > self.max1[b] {
+            self.max1[k] = self.max1[a];
+            self.max2[k] = self.max2[a].max(self.max1[b]);
+            self.max_cnt[k] = self.max_cnt[a];
+        } else {
+            self.max1[k] = self.max1[b];
+            self.max2[k] = self.max1[a].max(self.max2[b]);
+            self.max_cnt[k] = self.max_cnt[b];
+        }
+
+        if self.min1[a] == self.min1[b] {
+            self.min1[k] = self.min1[a];
+            self.min2[k] = self.min2[a].min(self.min2[b]);
+            self.min_cnt[k] = self.min_cnt[a] + self.min_cnt[b];
+        } else if self.min1[a] < self.min1[b] {
+            self.min1[k] = self.min1[a];
+            self.min2[k] = self.min2[a].min(self.min1[b]);
+            self.min_cnt[k] = self.min_cnt[a];
+        } else {
+            self.min1[k] = self.min1[b];
+            self.min2[k] = self.min1[a].min(self.min2[b]);
+            self.min_cnt[k] = self.min_cnt[b];
+        }
+    }
+
+    /// Lowers the maximum of node `k` to `x` (requires `max2 < x < max1`).
+    fn chmin_node(
+        &mut self,
+        k: usize,
+        x: i64,
+    ) {
+        self.sum[k] += (x - self.max1[k]) * self.max_cnt[k];
+        if self.max1[k] == self.min1[k] {
+            self.min1[k] = x;
+        } else if self.max1[k] == self.min2[k] {
+            self.min2[k] = x;
+        }
+        self.max1[k] = x;
+    }
+
+    /// Raises the minimum of node `k` to `x` (requires `min1 < x < min2`).
+    fn chmax_node(
+        &mut self,
+        k: usize,
+        x: i64,
+    ) {
+        self.sum[k] += (x - self.min1[k]) * self.min_cnt[k];
+        if self.min1[k] == self.max1[k] {
+            self.max1[k] = x;
+        } else if self.min1[k] == self.max2[k] {
+            self.max2[k] = x;
+        }
+        self.min1[k] = x;
+    }
+
+    fn add_node(
+        &mut self,
+        k: usize,
+        x: i64,
+    ) {
+        if self.len[k] == 0 {
+            return;
+        }
+        self.sum[k] += x * self.len[k];
+        self.max1[k] += x;
+        if self.max2[k] != Self::NEG_INF {
+            self.max2[k] += x;
+        }
+        self.min1[k] += x;
+        if self.min2[k] != Self::POS_INF {
+            self.min2[k] += x;
+        }
+        self.lazy_add[k] += x;
+    }
+
+    fn push(
+        &mut self,
+        k: usize,
+    ) {
+        if self.lazy_add[k] != 0 {
+            let x = self.lazy_add[k];
+            self.add_node(2 * k, x);
+            self.add_node(2 * k + 1, x);
+            self.lazy_add[k] = 0;
+        }
+        for c in [2 * k, 2 * k + 1] {
+            if self.max1[k] < self.max1[c] {
+                self.chmin_node(c, self.max1[k]);
+            }
+            if self.min1[k] > self.min1[c] {
+                self.chmax_node(c, self.min1[k]);
+            }
+        }
+    }
+
+    fn chmin_recursive(
+        &mut self,
+        k: usize,
+        l: usize,
+        r: usize,
+        ql: usize,
+        qr: usize,
+        x: i64,
+    ) {
+        if qr <= l || r <= ql || self.max1[k] <= x {
+            return;
+        }
+        if ql <= l && r <= qr && self.max2[k] < x {
+            self.chmin_node(k, x);
+            return;
+        }
+        self.push(k);
+        let mid = (l + r) / 2;
+        self.chmin_recursive(2 * k, l, mid, ql, qr, x);
+        self.chmin_recursive(2 * k + 1, mid, r, ql, qr, x);
+        self.update(k);
+    }
+
+    fn chmax_recursive(
+        &mut self,
+        k: usize,
+        l: usize,
+        r: usize,
+        ql: usize,
+        qr: usize,
+        x: i64,
+    ) {
+        if qr <= l || r <= ql || self.min1[k] >= x {
+            return;
+        }
+        if ql <= l && r <= qr && self.min2[k] > x {
+            self.chmax_node(k, x);
+            return;
+        }
+        self.push(k);
+        let mid = (l + r) / 2;
+        self.chmax_recursive(2 * k, l, mid, ql, qr, x);
+        self.chmax_recursive(2 * k + 1, mid, r, ql, qr, x);
+        self.update(k);
+    }
+
+    fn add_recursive(
+        &mut self,
+        k: usize,
+        l: usize,
+        r: usize,
+        ql: usize,
+        qr: usize,
+        x: i64,
+    ) {
+        if qr <= l || r <= ql {
+            return;
+        }
+        if ql <= l && r <= qr {
+            self.add_node(k, x);
+            return;
+        }
+        self.push(k);
+        let mid = (l + r) / 2;
+        self.add_recursive(2 * k, l, mid, ql, qr, x);
+        self.add_recursive(2 * k + 1, mid, r, ql, qr, x);
+        self.update(k);
+    }
+
+    /// Returns (sum, min, max) of `[ql, qr)`.
+    fn query_recursive(
+        &mut self,
+        k: usize,
+        l: usize,
+        r: usize,
+        ql: usize,
+        qr: usize,
+    ) -> (i64, i64, i64) {
+        if qr <= l || r <= ql {
+            return (0, Self::POS_INF, Self::NEG_INF);
+        }
+        if ql <= l && r <= qr {
+            return (self.sum[k], self.min1[k], self.max1[k]);
+        }
+        self.push(k);
+        let mid = (l + r) / 2;
+        let a = self.query_recursive(2 * k, l, mid, ql, qr);
+        let b = self.query_recursive(2 * k + 1, mid, r, ql, qr);
+        (a.0 + b.0, a.1.min(b.1), a.2.max(b.2))
+    }
+}
+
+// --- SNAP END ---
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segment_tree_beats_random() {
+        let mut seed: u64 = 0x2545F4914F6CDD1D;
+        let mut next = |m: u64| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed % m
+        };
+        for n in [1, 2, 5, 8, 13] {
+            let mut naive: Vec<i64> = (0..n).map(|_| next(21) as i64 - 10).collect();
+            let mut st = SegmentTreeBeats::new(&naive);
+            for _ in 0..2000 {
+                let mut l = next(n as u64 + 1) as usize;
+                let mut r = next(n as u64 + 1) as usize;
+                if l > r {
+                    std::mem::swap(&mut l, &mut r);
+                }
+                let x = next(41) as i64 - 20;
+                match next(7) {
+                    0 => {
+                        st.chmin(l, r, x);
+                        naive[l..r].iter_mut().for_each(|v| *v = (*v).min(x));
+                    }
+                    1 => {
+                        st.chmax(l, r, x);
+                        naive[l..r].iter_mut().for_each(|v| *v = (*v).max(x));
+                    }
+                    2 => {
+                        st.add(l, r, x);
+                        naive[l..r].iter_mut().for_each(|v| *v += x);
+                    }
+                    3 => {
+                        st.assign(l, r, x);
+                        naive[l..r].iter_mut().for_each(|v| *v = x);
+                    }
+                    4 => assert_eq!(st.sum(l, r), naive[l..r].iter().sum::<i64>()),
+                    5 => assert_eq!(st.min(l, r), naive[l..r].iter().copied().min().unwrap_or(i64::MAX)),
+                    _ => assert_eq!(st.max(l, r), naive[l..r].iter().copied().max().unwrap_or(i64::MIN)),
+                }
+            }
+            for (i, &v) in naive.iter().enumerate() {
+                assert_eq!(st.get(i), v);
+            }
+        }
+    }
+}
@@ -0,0 +1,29 @@
+#![allow(dead_code)]
+
+// INJECT: src/template/data_structures/segment_tree.rs
+// INJECT: src/template/data_structures/monoid.rs
+
+// --- SNAP START ---
+
+/// Segment Tree built from a `Monoid` implementation.
+pub type MonoidSegmentTree<M> = SegmentTree<<M as Monoid>::S, fn(<M as Monoid>::S, <M as Monoid>::S) -> <M as Monoid>::S>;
+
+impl<T> SegmentTree<T, fn(T, T) -> T>
+where
+    T: Clone + std::fmt::Debug,
+{
+    /// Creates a new SegmentTree from the given data, using the operation and identity of `M`.
+    ///
+    /// # Complexity
+    /// - O(N)
+    pub fn from_monoid<M: Monoid<S = T>>(data: &[T]) -> Self {
+        Self::new(data, |a, b| M::binary_operation(&a, &b), M::identity())
+    }
+}
+
+// --- SNAP END ---
+
+#[allow(unused_imports)]
+use super::monoid::Monoid;
+#[allow(unused_imports)]
+use super::segment_tree::SegmentTree;
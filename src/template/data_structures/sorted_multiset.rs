@@ -0,0 +1,280 @@
+#![allow(dead_code)]
+
+// --- SNAP START ---
+
+/// Sorted Multiset (Order-Statistic Multiset, Bucketed Sorted List)
+///
+/// An ordered multiset that also answers "k-th element" and "how many elements < x".
+/// Keys need not be known in advance (no coordinate compression).
+///
+/// Elements are kept in sorted buckets of bounded size; a bucket is split in half when it grows too large.
+///
+/// # Complexity
+/// - `insert` / `erase_one`: O(log N + B)
+/// - `kth` / `rank`: O(N / B + log B)
+/// - `count` / `lower_bound` / `upper_bound` / `contains`: O(log N)
+///
+/// where B is the bucket size (`SortedMultiset::BUCKET_SIZE`).
+///
+/// # Examples
+///
+/// ```
+/// let mut ms = SortedMultiset::new();
+/// for x in [5, 1, 4, 1, 3] {
+///     ms.insert(x);
+/// }
+/// assert_eq!(ms.len(), 5);
+/// assert_eq!(ms.kth(0), Some(&1));
+/// assert_eq!(ms.kth(3), Some(&4));
+/// assert_eq!(ms.rank(&4), 3); // {1, 1, 3}
+/// assert_eq!(ms.count(&1), 2);
+/// assert_eq!(ms.lower_bound(&2), Some(&3));
+/// assert_eq!(ms.upper_bound(&4), Some(&5));
+///
+/// assert!(ms.erase_one(&1));
+/// assert_eq!(ms.count(&1), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SortedMultiset<T> {
+    buckets: Vec<Vec<T>>,
+    len: usize,
+}
+
+impl<T: Ord + Clone> Default for SortedMultiset<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord + Clone> SortedMultiset<T> {
+    const BUCKET_SIZE: usize = 512;
+
+    pub fn new() -> Self {
+        SortedMultiset {
+            buckets: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// Builds a multiset from the given values.
+    ///
+    /// # Complexity
+    /// - O(N log N)
+    pub fn from_vec(mut a: Vec<T>) -> Self {
+        a.sort();
+        let len = a.len();
+        let buckets = a.chunks(Self::BUCKET_SIZE).map(|c| c.to_vec()).collect();
+        SortedMultiset { buckets, len }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the index of the bucket where `x` should be inserted
+    /// (the first bucket whose last element is >= `x`, or the last bucket).
+    fn bucket_index(
+        &self,
+        x: &T,
+    ) -> usize {
+        let i = self.buckets.partition_point(|b| b.last().unwrap() < x);
+        i.min(self.buckets.len().saturating_sub(1))
+    }
+
+    /// Inserts `x`.
+    pub fn insert(
+        &mut self,
+        x: T,
+    ) {
+        self.len += 1;
+        if self.buckets.is_empty() {
+            self.buckets.push(vec![x]);
+            return;
+        }
+        let i = self.bucket_index(&x);
+        let bucket = &mut self.buckets[i];
+        let pos = bucket.partition_point(|y| *y <= x);
+        bucket.insert(pos, x);
+        if bucket.len() > 2 * Self::BUCKET_SIZE {
+            let right = bucket.split_off(Self::BUCKET_SIZE);
+            self.buckets.insert(i + 1, right);
+        }
+    }
+
+    /// Removes one occurrence of `x`. Returns `false` if `x` is not contained.
+    pub fn erase_one(
+        &mut self,
+        x: &T,
+    ) -> bool {
+        if self.buckets.is_empty() {
+            return false;
+        }
+        let i = self.bucket_index(x);
+        let bucket = &mut self.buckets[i];
+        let pos = bucket.partition_point(|y| y < x);
+        if pos == bucket.len() || bucket[pos] != *x {
+            return false;
+        }
+        bucket.remove(pos);
+        if bucket.is_empty() {
+            self.buckets.remove(i);
+        }
+        self.len -= 1;
+        true
+    }
+
+    /// Returns the number of elements less than `x`.
+    pub fn rank(
+        &self,
+        x: &T,
+    ) -> usize {
+        let mut res = 0;
+        for bucket in &self.buckets {
+            if bucket.last().unwrap() < x {
+                res += bucket.len();
+            } else {
+                return res + bucket.partition_point(|y| y < x);
+            }
+        }
+        res
+    }
+
+    /// Returns the number of elements equal to `x`.
+    pub fn count(
+        &self,
+        x: &T,
+    ) -> usize {
+        // Equal elements may span several buckets
+        let start = self.buckets.partition_point(|b| b.last().unwrap() < x);
+        let mut res = 0;
+        for bucket in &self.buckets[start..] {
+            let lo = bucket.partition_point(|y| y < x);
+            let hi = bucket.partition_point(|y| y <= x);
+            res += hi - lo;
+            if hi < bucket.len() {
+                break;
+            }
+        }
+        res
+    }
+
+    pub fn contains(
+        &self,
+        x: &T,
+    ) -> bool {
+        self.lower_bound(x) == Some(x)
+    }
+
+    /// Returns the `k`-th (0-indexed) smallest element.
+    pub fn kth(
+        &self,
+        mut k: usize,
+    ) -> Option<&T> {
+        for bucket in &self.buckets {
+            if k < bucket.len() {
+                return Some(&bucket[k]);
+            }
+            k -= bucket.len();
+        }
+        None
+    }
+
+    /// Returns the smallest element >= `x`.
+    pub fn lower_bound(
+        &self,
+        x: &T,
+    ) -> Option<&T> {
+        let i = self.buckets.partition_point(|b| b.last().unwrap() < x);
+        self.buckets.get(i).map(|b| &b[b.partition_point(|y| y < x)])
+    }
+
+    /// Returns the smallest element > `x`.
+    pub fn upper_bound(
+        &self,
+        x: &T,
+    ) -> Option<&T> {
+        let i = self.buckets.partition_point(|b| b.last().unwrap() <= x);
+        self.buckets.get(i).map(|b| &b[b.partition_point(|y| y <= x)])
+    }
+
+    /// Returns the largest element < `x`.
+    pub fn prev(
+        &self,
+        x: &T,
+    ) -> Option<&T> {
+        let i = self.buckets.partition_point(|b| b[0] < *x);
+        if i == 0 {
+            return None;
+        }
+        let b = &self.buckets[i - 1];
+        Some(&b[b.partition_point(|y| y < x) - 1])
+    }
+
+    pub fn min(&self) -> Option<&T> {
+        self.buckets.first().map(|b| &b[0])
+    }
+
+    pub fn max(&self) -> Option<&T> {
+        self.buckets.last().map(|b| b.last().unwrap())
+    }
+
+    /// Iterates over all elements in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.buckets.iter().flatten()
+    }
+}
+
+// --- SNAP END ---
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sorted_multiset_random() {
+        let mut seed: u64 = 0xDEAD_BEEF_CAFE_BABE;
+        let mut next = |m: u64| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed % m
+        };
+        let mut ms = SortedMultiset::new();
+        let mut naive: Vec<i64> = Vec::new();
+        for step in 0..20000 {
+            // Small key range so that equal elements span several buckets
+            let x = next(if step < 10000 { 50 } else { 100000 }) as i64;
+            match next(4) {
+                0 | 1 => {
+                    ms.insert(x);
+                    let pos = naive.partition_point(|&y| y <= x);
+                    naive.insert(pos, x);
+                }
+                2 => {
+                    let expected = naive.binary_search(&x).is_ok();
+                    if expected {
+                        let pos = naive.partition_point(|&y| y < x);
+                        naive.remove(pos);
+                    }
+                    assert_eq!(ms.erase_one(&x), expected);
+                }
+                _ => {
+                    assert_eq!(ms.rank(&x), naive.partition_point(|&y| y < x));
+                    assert_eq!(ms.count(&x), naive.iter().filter(|&&y| y == x).count());
+                    assert_eq!(ms.lower_bound(&x), naive.iter().find(|&&y| y >= x));
+                    assert_eq!(ms.upper_bound(&x), naive.iter().find(|&&y| y > x));
+                    assert_eq!(ms.prev(&x), naive.iter().rev().find(|&&y| y < x));
+                    let k = next(naive.len() as u64 + 1) as usize;
+                    assert_eq!(ms.kth(k), naive.get(k));
+                }
+            }
+            assert_eq!(ms.len(), naive.len());
+        }
+        assert!(ms.iter().eq(naive.iter()));
+        assert_eq!(SortedMultiset::from_vec(naive.clone()).iter().count(), naive.len());
+    }
+}
@@ -0,0 +1,212 @@
+#![allow(dead_code)]
+
+// --- SNAP START ---
+
+/// Sparse Table (Static Range Query for Idempotent Operations)
+///
+/// Answers range queries in O(1) after O(N log N) preprocessing.
+/// The operation must be associative and idempotent (`op(x, x) = x`): min, max, gcd, and, or.
+/// Takes the same `(data, op, identity)` description as `SegmentTree::new`.
+///
+/// # Examples
+///
+/// ```
+/// let data = vec![3, 1, 4, 1, 5, 9, 2, 6];
+/// let st = SparseTable::new(&data, |a: i64, b: i64| a.min(b), i64::MAX);
+/// assert_eq!(st.query(2, 5), 1);
+/// assert_eq!(st.query(4, 6), 5);
+///
+/// // Same as `SegmentTree::from_monoid` (inject `sparse_table_monoid.rs`)
+/// let st = SparseTable::from_monoid::<Max<i64>>(&data);
+/// assert_eq!(st.query(0, 8), 9);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SparseTable<T, F> {
+    n: usize,
+    table: Vec<Vec<T>>,
+    op: F,
+    identity: T,
+}
+
+impl<T, F> SparseTable<T, F>
+where
+    T: Clone,
+    F: Fn(T, T) -> T,
+{
+    /// # Complexity
+    /// - O(N log N)
+    pub fn new(
+        data: &[T],
+        op: F,
+        identity: T,
+    ) -> Self {
+        let n = data.len();
+        let mut table = vec![data.to_vec()];
+        let mut k = 1;
+        while 2 * k <= n {
+            let prev = table.last().unwrap();
+            let next: Vec<T> = (0..=n - 2 * k)
+                .map(|i| op(prev[i].clone(), prev[i + k].clone()))
+                .collect();
+            table.push(next);
+            k *= 2;
+        }
+        SparseTable {
+            n,
+            table,
+            op,
+            identity,
+        }
+    }
+
+    /// Queries the result of the operation over `[l, r)`.
+    /// Returns the identity element if the range is empty.
+    ///
+    /// # Complexity
+    /// - O(1)
+    pub fn query(
+        &self,
+        l: usize,
+        r: usize,
+    ) -> T {
+        assert!(l <= r && r <= self.n, "Invalid range");
+        if l == r {
+            return self.identity.clone();
+        }
+        let k = (r - l).ilog2() as usize;
+        (self.op)(self.table[k][l].clone(), self.table[k][r - (1 << k)].clone())
+    }
+}
+
+/// Disjoint Sparse Table (Static Range Query for any Monoid)
+///
+/// Answers range queries in O(1) after O(N log N) preprocessing.
+/// Only associativity is required, so it works for sums, matrix products and ModInt products.
+/// Takes the same `(data, op, identity)` description as `SegmentTree::new`.
+///
+/// # Examples
+///
+/// ```
+/// let data = vec![3, 1, 4, 1, 5, 9, 2, 6];
+/// let dst = DisjointSparseTable::new(&data, |a: i64, b: i64| a + b, 0);
+/// assert_eq!(dst.query(2, 5), 10);
+///
+/// // Non-commutative: affine composition, applied left to right (inject `sparse_table_monoid.rs`)
+/// let fs = vec![(2, 1), (3, 0), (1, 5)];
+/// let dst = DisjointSparseTable::from_monoid::<Affine<i64>>(&fs);
+/// assert_eq!(dst.query(0, 3), (6, 8));
+/// ```
+#[derive(Debug, Clone)]
+pub struct DisjointSparseTable<T, F> {
+    n: usize,
+    data: Vec<T>,
+    table: Vec<Vec<T>>,
+    op: F,
+    identity: T,
+}
+
+impl<T, F> DisjointSparseTable<T, F>
+where
+    T: Clone,
+    F: Fn(T, T) -> T,
+{
+    /// # Complexity
+    /// - O(N log N)
+    pub fn new(
+        data: &[T],
+        op: F,
+        identity: T,
+    ) -> Self {
+        let n = data.len();
+        let mut size = 2;
+        while size < n {
+            size *= 2;
+        }
+        let mut padded = data.to_vec();
+        padded.resize(size, identity.clone());
+        let log = size.trailing_zeros() as usize;
+
+        // table[h]: blocks of size 2^(h+1), split at the middle.
+        // Left half stores suffix products up to the middle, right half stores prefix products from the middle.
+        let mut table = vec![padded.clone(); log];
+        for (h, row) in table.iter_mut().enumerate() {
+            let half = 1 << h;
+            for start in (0..size).step_by(2 * half) {
+                let mid = start + half;
+                for i in (start..mid - 1).rev() {
+                    row[i] = op(padded[i].clone(), row[i + 1].clone());
+                }
+                for i in mid + 1..start + 2 * half {
+                    row[i] = op(row[i - 1].clone(), padded[i].clone());
+                }
+            }
+        }
+        DisjointSparseTable {
+            n,
+            data: padded,
+            table,
+            op,
+            identity,
+        }
+    }
+
+    /// Queries the result of the operation over `[l, r)`.
+    /// Returns the identity element if the range is empty.
+    ///
+    /// # Complexity
+    /// - O(1)
+    pub fn query(
+        &self,
+        l: usize,
+        r: usize,
+    ) -> T {
+        assert!(l <= r && r <= self.n, "Invalid range");
+        if l == r {
+            return self.identity.clone();
+        }
+        let r = r - 1;
+        if l == r {
+            return self.data[l].clone();
+        }
+        let h = (l ^ r).ilog2() as usize;
+        (self.op)(self.table[h][l].clone(), self.table[h][r].clone())
+    }
+}
+
+// --- SNAP END ---
+
+#[cfg(test)]
+mod tests {
+    use super::super::monoid::{Affine, Gcd, Monoid};
+    use super::*;
+
+    #[test]
+    fn test_sparse_table() {
+        let data = vec![12u64, 18, 24, 7, 14, 21, 28, 30, 45];
+        let st = SparseTable::from_monoid::<Gcd<u64>>(&data);
+        let min = SparseTable::new(&data, |a: u64, b: u64| a.min(b), u64::MAX);
+        for l in 0..=data.len() {
+            for r in l..=data.len() {
+                let g = data[l..r].iter().fold(0, |a, &b| Gcd::<u64>::binary_operation(&a, &b));
+                assert_eq!(st.query(l, r), g);
+                assert_eq!(min.query(l, r), data[l..r].iter().copied().min().unwrap_or(u64::MAX));
+            }
+        }
+    }
+
+    #[test]
+    fn test_disjoint_sparse_table() {
+        for n in 0..20 {
+            let fs: Vec<(i64, i64)> = (0..n).map(|i| (i % 3 + 1, i - 4)).collect();
+            let dst = DisjointSparseTable::from_monoid::<Affine<i64>>(&fs);
+            for l in 0..=n as usize {
+                for r in l..=n as usize {
+                    let expected = fs[l..r]
+                        .iter()
+                        .fold((1, 0), |f, g| Affine::<i64>::binary_operation(&f, g));
+                    assert_eq!(dst.query(l, r), expected);
+                }
+            }
+        }
+    }
+}
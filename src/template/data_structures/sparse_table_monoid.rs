@@ -0,0 +1,33 @@
+#![allow(dead_code)]
+
+// INJECT: src/template/data_structures/sparse_table.rs
+// INJECT: src/template/data_structures/monoid.rs
+
+// --- SNAP START ---
+
+impl<T> SparseTable<T, fn(T, T) -> T>
+where
+    T: Clone,
+{
+    /// Creates a new SparseTable using the operation and identity of `M` (which must be idempotent).
+    pub fn from_monoid<M: Monoid<S = T>>(data: &[T]) -> Self {
+        Self::new(data, |a, b| M::binary_operation(&a, &b), M::identity())
+    }
+}
+
+impl<T> DisjointSparseTable<T, fn(T, T) -> T>
+where
+    T: Clone,
+{
+    /// Creates a new DisjointSparseTable using the operation and identity of `M`.
+    pub fn from_monoid<M: Monoid<S = T>>(data: &[T]) -> Self {
+        Self::new(data, |a, b| M::binary_operation(&a, &b), M::identity())
+    }
+}
+
+// --- SNAP END ---
+
+#[allow(unused_imports)]
+use super::monoid::Monoid;
+#[allow(unused_imports)]
+use super::sparse_table::{DisjointSparseTable, SparseTable};
@@ -20,12 +20,19 @@
 ///
 /// // Get component size
 /// println!("Size of 0: {}", uf.size(0));
+///
+/// // Enumerate components
+/// println!("{} components", uf.count_components());
+/// for group in uf.groups() {
+///     println!("{:?}", group);
+/// }
 /// ```
 
 pub struct UnionFind {
     parent: Vec<usize>,
     size: Vec<usize>,
     rank: Vec<usize>,
+    components: usize,
 }
 
 impl UnionFind {
@@ -34,6 +41,7 @@ impl UnionFind {
             parent: (0..n).collect(),
             size: vec![1; n],
             rank: vec![0; n],
+            components: n,
         }
     }
 
@@ -77,6 +85,7 @@ impl UnionFind {
                 self.rank[root_x] += 1;
             }
         }
+        self.components -= 1;
         true
     }
 
@@ -93,6 +102,7 @@ impl UnionFind {
             self.parent[root_y] = root_x;
             self.size[root_x] += self.size[root_y];
         }
+        self.components -= 1;
         true
     }
 
@@ -104,6 +114,327 @@ impl UnionFind {
     pub fn same(&mut self, x: usize, y: usize) -> bool {
         self.find(x) == self.find(y)
     }
+
+    /// Returns the number of connected components.
+    ///
+    /// # Complexity
+    /// - O(1)
+    pub fn count_components(&self) -> usize {
+        self.components
+    }
+
+    /// Returns the representatives (roots) of all components in increasing order.
+    ///
+    /// # Complexity
+    /// - O(N α(N))
+    pub fn roots(&mut self) -> Vec<usize> {
+        (0..self.parent.len()).filter(|&x| self.find(x) == x).collect()
+    }
+
+    /// Returns the vertices of each component.
+    /// Components are ordered by their smallest vertex, and vertices in each component are sorted.
+    ///
+    /// # Complexity
+    /// - O(N α(N))
+    pub fn groups(&mut self) -> Vec<Vec<usize>> {
+        let n = self.parent.len();
+        let mut index = vec![usize::MAX; n];
+        let mut groups: Vec<Vec<usize>> = Vec::with_capacity(self.components);
+        for x in 0..n {
+            let root = self.find(x);
+            if index[root] == usize::MAX {
+                index[root] = groups.len();
+                groups.push(Vec::with_capacity(self.size[root]));
+            }
+            groups[index[root]].push(x);
+        }
+        groups
+    }
+}
+
+/// Union-Find with per-component aggregates
+///
+/// Each vertex holds a value, and the values of a component are merged with `op` on `unite`.
+/// `op` must be associative and commutative (sum, min, max, gcd, ...).
+///
+/// # Usage
+/// ```
+/// let values = vec![5, 3, 8, 1];
+///
+/// // Sum of each component
+/// let mut uf = UnionFindAggregate::new(&values, |a, b| a + b);
+/// uf.unite(0, 1);
+/// assert_eq!(*uf.get(1), 8);
+///
+/// // Min of each component
+/// let mut uf = UnionFindAggregate::new(&values, |a: i64, b: i64| a.min(b));
+/// uf.unite(2, 3);
+/// assert_eq!(*uf.get(2), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct UnionFindAggregate<T, F> {
+    uf: UnionFind,
+    data: Vec<T>,
+    op: F,
+}
+
+impl<T, F> UnionFindAggregate<T, F>
+where
+    T: Clone,
+    F: Fn(T, T) -> T,
+{
+    pub fn new(values: &[T], op: F) -> Self {
+        UnionFindAggregate {
+            uf: UnionFind::new(values.len()),
+            data: values.to_vec(),
+            op,
+        }
+    }
+
+    pub fn find(&mut self, x: usize) -> usize {
+        self.uf.find(x)
+    }
+
+    /// Unites the sets containing `x` and `y` and merges their aggregates.
+    pub fn unite(&mut self, x: usize, y: usize) -> bool {
+        let root_x = self.uf.find(x);
+        let root_y = self.uf.find(y);
+        if !self.uf.unite(root_x, root_y) {
+            return false;
+        }
+        let root = self.uf.find(root_x);
+        self.data[root] = (self.op)(self.data[root_x].clone(), self.data[root_y].clone());
+        true
+    }
+
+    /// Returns the aggregate of the component containing `x`.
+    pub fn get(&mut self, x: usize) -> &T {
+        let root = self.uf.find(x);
+        &self.data[root]
+    }
+
+    /// Replaces the aggregate of the component containing `x` with `op(aggregate, value)`.
+    pub fn add(&mut self, x: usize, value: T) {
+        let root = self.uf.find(x);
+        self.data[root] = (self.op)(self.data[root].clone(), value);
+    }
+
+    pub fn size(&mut self, x: usize) -> usize {
+        self.uf.size(x)
+    }
+
+    pub fn same(&mut self, x: usize, y: usize) -> bool {
+        self.uf.same(x, y)
+    }
+
+    pub fn count_components(&self) -> usize {
+        self.uf.count_components()
+    }
+
+    pub fn groups(&mut self) -> Vec<Vec<usize>> {
+        self.uf.groups()
+    }
+}
+
+/// Trait for an abelian group, used as the potential type of `WeightedUnionFind`.
+pub trait Group {
+    /// The type of the elements in the group.
+    type S: Copy + PartialEq + std::fmt::Debug;
+    /// The identity element of the group.
+    fn identity() -> Self::S;
+    /// The (commutative) binary operation.
+    fn binary_operation(a: &Self::S, b: &Self::S) -> Self::S;
+    /// The inverse element of `a`.
+    fn inverse(a: &Self::S) -> Self::S;
+}
+
+/// Additive group over `T` (i64, ModInt, ...).
+pub struct Additive<T>(std::marker::PhantomData<T>);
+
+impl<T> Group for Additive<T>
+where
+    T: Copy + PartialEq + std::fmt::Debug + Default + std::ops::Add<Output = T> + std::ops::Neg<Output = T>,
+{
+    type S = T;
+    fn identity() -> T {
+        T::default()
+    }
+    fn binary_operation(a: &T, b: &T) -> T {
+        *a + *b
+    }
+    fn inverse(a: &T) -> T {
+        -*a
+    }
+}
+
+/// XOR group over `T` (u64, usize, ...).
+pub struct Xor<T>(std::marker::PhantomData<T>);
+
+impl<T> Group for Xor<T>
+where
+    T: Copy + PartialEq + std::fmt::Debug + Default + std::ops::BitXor<Output = T>,
+{
+    type S = T;
+    fn identity() -> T {
+        T::default()
+    }
+    fn binary_operation(a: &T, b: &T) -> T {
+        *a ^ *b
+    }
+    fn inverse(a: &T) -> T {
+        *a
+    }
+}
+
+/// Error returned by `WeightedUnionFind::unite` when the new constraint contradicts the existing ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Conflict<S> {
+    /// The difference `x_v - x_u` implied by the existing constraints.
+    pub existing: S,
+    /// The difference `x_v - x_u` that was requested.
+    pub requested: S,
+}
+
+/// Weighted Union-Find (Potential Union-Find)
+///
+/// Maintains constraints of the form `x_v - x_u = w` on a group `G`
+/// and answers the difference between any two connected vertices.
+///
+/// # Usage
+/// ```
+/// // Additive potentials over i64 (default)
+/// let mut uf = WeightedUnionFind::<Additive<i64>>::new(n);
+///
+/// // x_1 - x_0 = 3, x_2 - x_1 = 4
+/// assert_eq!(uf.unite(0, 1, 3), Ok(true));
+/// assert_eq!(uf.unite(1, 2, 4), Ok(true));
+/// assert_eq!(uf.diff(0, 2), Some(7));
+///
+/// // Already implied: returns Ok(false)
+/// assert_eq!(uf.unite(0, 2, 7), Ok(false));
+///
+/// // Contradiction
+/// assert!(uf.unite(2, 0, 1).is_err());
+///
+/// // XOR potentials
+/// let mut uf = WeightedUnionFind::<Xor<u64>>::new(n);
+/// ```
+#[derive(Debug, Clone)]
+pub struct WeightedUnionFind<G: Group = Additive<i64>> {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    /// Potential relative to the parent: x_v = x_parent + diff_weight[v]
+    diff_weight: Vec<G::S>,
+}
+
+impl<G: Group> WeightedUnionFind<G> {
+    pub fn new(n: usize) -> Self {
+        WeightedUnionFind {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+            diff_weight: vec![G::identity(); n],
+        }
+    }
+
+    pub fn find(
+        &mut self,
+        x: usize,
+    ) -> usize {
+        if self.parent[x] == x {
+            x
+        } else {
+            let p = self.parent[x];
+            let root = self.find(p);
+            // diff_weight[p] is now relative to root
+            self.diff_weight[x] = G::binary_operation(&self.diff_weight[p], &self.diff_weight[x]);
+            self.parent[x] = root;
+            root
+        }
+    }
+
+    /// Returns the potential of `x` relative to its root.
+    pub fn weight(
+        &mut self,
+        x: usize,
+    ) -> G::S {
+        self.find(x);
+        self.diff_weight[x]
+    }
+
+    /// Adds the constraint `x_v - x_u = w`.
+    ///
+    /// # Returns
+    /// - `Ok(true)` if two different components were merged.
+    /// - `Ok(false)` if `u` and `v` were already connected and the constraint is consistent.
+    /// - `Err(Conflict)` if the constraint contradicts the existing ones. The structure is not modified.
+    ///
+    /// # Complexity
+    /// - O(α(N)) amortized
+    pub fn unite(
+        &mut self,
+        u: usize,
+        v: usize,
+        w: G::S,
+    ) -> Result<bool, Conflict<G::S>> {
+        let root_u = self.find(u);
+        let root_v = self.find(v);
+        let wu = self.diff_weight[u];
+        let wv = self.diff_weight[v];
+        if root_u == root_v {
+            let existing = G::binary_operation(&wv, &G::inverse(&wu));
+            return if existing == w {
+                Ok(false)
+            } else {
+                Err(Conflict {
+                    existing,
+                    requested: w,
+                })
+            };
+        }
+        // x_{root_v} - x_{root_u} = wu + w - wv
+        let d = G::binary_operation(&G::binary_operation(&wu, &w), &G::inverse(&wv));
+        if self.size[root_u] < self.size[root_v] {
+            self.parent[root_u] = root_v;
+            self.size[root_v] += self.size[root_u];
+            self.diff_weight[root_u] = G::inverse(&d);
+        } else {
+            self.parent[root_v] = root_u;
+            self.size[root_u] += self.size[root_v];
+            self.diff_weight[root_v] = d;
+        }
+        Ok(true)
+    }
+
+    /// Returns `x_v - x_u` if `u` and `v` are connected, otherwise `None`.
+    ///
+    /// # Complexity
+    /// - O(α(N)) amortized
+    pub fn diff(
+        &mut self,
+        u: usize,
+        v: usize,
+    ) -> Option<G::S> {
+        if !self.same(u, v) {
+            return None;
+        }
+        Some(G::binary_operation(&self.diff_weight[v], &G::inverse(&self.diff_weight[u])))
+    }
+
+    pub fn size(
+        &mut self,
+        x: usize,
+    ) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    pub fn same(
+        &mut self,
+        x: usize,
+        y: usize,
+    ) -> bool {
+        self.find(x) == self.find(y)
+    }
 }
 
 // --- SNAP END ---
@@ -119,4 +450,81 @@ mod tests {
         assert!(uf.same(0, 1));
         assert!(!uf.same(0, 2));
     }
+
+    #[test]
+    fn test_union_find_groups() {
+        let mut uf = UnionFind::new(6);
+        assert_eq!(uf.count_components(), 6);
+        uf.unite(4, 1);
+        uf.unite(0, 3);
+        uf.unite(3, 4);
+        uf.unite(1, 0);
+        assert_eq!(uf.count_components(), 3);
+        assert_eq!(uf.groups(), vec![vec![0, 1, 3, 4], vec![2], vec![5]]);
+
+        let roots = uf.roots();
+        assert_eq!(roots.len(), 3);
+        assert_eq!(roots.iter().map(|&r| uf.size(r)).sum::<usize>(), 6);
+
+        uf.unite_by_size(2, 5);
+        assert_eq!(uf.count_components(), 2);
+        assert_eq!(uf.groups(), vec![vec![0, 1, 3, 4], vec![2, 5]]);
+    }
+
+    #[test]
+    fn test_union_find_aggregate() {
+        let values = vec![5i64, 3, 8, 1, 7];
+        let mut sum = UnionFindAggregate::new(&values, |a, b| a + b);
+        let mut max = UnionFindAggregate::new(&values, |a: i64, b: i64| a.max(b));
+        for (x, y) in [(0, 1), (3, 4), (1, 0), (4, 0)] {
+            sum.unite(x, y);
+            max.unite(x, y);
+        }
+        assert_eq!(*sum.get(3), 16);
+        assert_eq!(*sum.get(2), 8);
+        assert_eq!(*max.get(1), 7);
+        assert_eq!(sum.count_components(), 2);
+
+        sum.add(2, 10);
+        assert_eq!(*sum.get(2), 18);
+    }
+
+    #[test]
+    fn test_weighted_union_find() {
+        let mut uf = WeightedUnionFind::<Additive<i64>>::new(5);
+        assert_eq!(uf.unite(0, 1, 3), Ok(true));
+        assert_eq!(uf.unite(2, 1, -2), Ok(true));
+        assert_eq!(uf.unite(3, 4, 10), Ok(true));
+        assert_eq!(uf.diff(0, 2), Some(5));
+        assert_eq!(uf.diff(2, 0), Some(-5));
+        assert_eq!(uf.diff(0, 3), None);
+
+        assert_eq!(uf.unite(4, 0, 1), Ok(true));
+        assert_eq!(uf.diff(3, 2), Some(16));
+        assert_eq!(uf.size(2), 5);
+
+        // Consistent constraint between already connected vertices
+        assert_eq!(uf.unite(3, 2, 16), Ok(false));
+        // Inconsistent constraint
+        assert_eq!(
+            uf.unite(3, 2, 15),
+            Err(Conflict {
+                existing: 16,
+                requested: 15
+            })
+        );
+        assert_eq!(uf.diff(3, 2), Some(16));
+    }
+
+    #[test]
+    fn test_weighted_union_find_xor() {
+        // Parity constraints: x_u xor x_v = w
+        let mut uf = WeightedUnionFind::<Xor<u64>>::new(4);
+        assert_eq!(uf.unite(0, 1, 1), Ok(true));
+        assert_eq!(uf.unite(1, 2, 1), Ok(true));
+        assert_eq!(uf.diff(0, 2), Some(0));
+        assert!(uf.unite(2, 0, 1).is_err());
+        assert_eq!(uf.unite(2, 3, 1), Ok(true));
+        assert_eq!(uf.diff(3, 0), Some(1));
+    }
 }
\ No newline at end of file
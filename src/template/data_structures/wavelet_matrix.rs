@@ -0,0 +1,344 @@
+#![allow(dead_code)]
+
+// --- SNAP START ---
+
+/// Bit vector with O(1) rank queries, used by `WaveletMatrix`.
+#[derive(Debug, Clone)]
+struct BitVector {
+    bits: Vec<u64>,
+    // cum[i]: number of ones in bits[0..i]
+    cum: Vec<usize>,
+}
+
+impl BitVector {
+    fn new(bits: &[bool]) -> Self {
+        let mut words = vec![0u64; bits.len() / 64 + 1];
+        for (i, &b) in bits.iter().enumerate() {
+            if b {
+                words[i >> 6] |= 1 << (i & 63);
+            }
+        }
+        let mut cum = vec![0; words.len() + 1];
+        for i in 0..words.len() {
+            cum[i + 1] = cum[i] + words[i].count_ones() as usize;
+        }
+        BitVector { bits: words, cum }
+    }
+
+    /// Number of ones in `[0, i)`.
+    fn rank1(
+        &self,
+        i: usize,
+    ) -> usize {
+        let mask = (1u64 << (i & 63)) - 1;
+        self.cum[i >> 6] + (self.bits[i >> 6] & mask).count_ones() as usize
+    }
+
+    /// Number of zeros in `[0, i)`.
+    fn rank0(
+        &self,
+        i: usize,
+    ) -> usize {
+        i - self.rank1(i)
+    }
+}
+
+/// Wavelet Matrix (Static Range k-th / Range Frequency Queries)
+///
+/// Answers order-statistic queries on subarrays of a static `u64` array.
+/// The number of levels is the bit width of the maximum value, not 64.
+///
+/// Build with `with_sum` to also answer `sum_below` (the sum of values below a bound in a range);
+/// since the array is static, each level keeps plain prefix sums instead of a BIT.
+///
+/// # Examples
+///
+/// ```
+/// let wm = WaveletMatrix::new(&[5, 1, 4, 1, 3, 9, 2]);
+/// assert_eq!(wm.kth_smallest(0, 5, 2), 3); // sorted [1, 1, 3, 4, 5]
+/// assert_eq!(wm.range_freq(1, 6, 2, 5), 2); // {4, 3}
+/// assert_eq!(wm.prev_value(0, 7, 5), Some(4)); // largest value < 5
+/// assert_eq!(wm.next_value(0, 7, 6), Some(9)); // smallest value >= 6
+///
+/// let wm = WaveletMatrix::with_sum(&[5, 1, 4, 1, 3, 9, 2]);
+/// assert_eq!(wm.sum_below(0, 7, 5), 11); // 1 + 4 + 1 + 3 + 2
+/// ```
+///
+/// # Complexity
+/// - Construction: O(N log V)
+/// - Queries: O(log V)
+#[derive(Debug, Clone)]
+pub struct WaveletMatrix {
+    n: usize,
+    log: usize,
+    // levels[d]: bits of the (log - 1 - d)-th bit, in the order at depth d
+    levels: Vec<BitVector>,
+    // mids[d]: number of zeros at depth d
+    mids: Vec<usize>,
+    // sums[d]: prefix sums of the values in the order at depth d (only with `with_sum`)
+    sums: Option<Vec<Vec<u64>>>,
+}
+
+impl WaveletMatrix {
+    /// # Complexity
+    /// - O(N log V)
+    pub fn new(a: &[u64]) -> Self {
+        Self::build(a, false)
+    }
+
+    /// Creates a new WaveletMatrix that also supports `sum_below`.
+    ///
+    /// # Complexity
+    /// - O(N log V) time and memory
+    pub fn with_sum(a: &[u64]) -> Self {
+        Self::build(a, true)
+    }
+
+    fn build(
+        a: &[u64],
+        with_sum: bool,
+    ) -> Self {
+        let n = a.len();
+        let max = a.iter().copied().max().unwrap_or(0);
+        let log = (64 - max.leading_zeros() as usize).max(1);
+        let mut levels = Vec::with_capacity(log);
+        let mut mids = Vec::with_capacity(log);
+        let mut sums = Vec::new();
+        let mut cur = a.to_vec();
+
+        let prefix_sum = |v: &[u64]| {
+            let mut s = vec![0; v.len() + 1];
+            for (i, &x) in v.iter().enumerate() {
+                s[i + 1] = s[i] + x;
+            }
+            s
+        };
+
+        for d in 0..log {
+            let bit = log - 1 - d;
+            if with_sum {
+                sums.push(prefix_sum(&cur));
+            }
+            let bits: Vec<bool> = cur.iter().map(|&x| (x >> bit) & 1 == 1).collect();
+            levels.push(BitVector::new(&bits));
+            let (zeros, ones): (Vec<u64>, Vec<u64>) = cur.iter().partition(|&&x| (x >> bit) & 1 == 0);
+            mids.push(zeros.len());
+            cur = zeros;
+            cur.extend(ones);
+        }
+        if with_sum {
+            sums.push(prefix_sum(&cur));
+        }
+
+        WaveletMatrix {
+            n,
+            log,
+            levels,
+            mids,
+            sums: if with_sum { Some(sums) } else { None },
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Returns the `k`-th (0-indexed) smallest value in `a[l..r]`.
+    ///
+    /// # Panics
+    /// Panics if `k >= r - l`.
+    pub fn kth_smallest(
+        &self,
+        mut l: usize,
+        mut r: usize,
+        mut k: usize,
+    ) -> u64 {
+        assert!(l <= r && r <= self.n, "Invalid range");
+        assert!(k < r - l, "k is out of range");
+        let mut res = 0;
+        for d in 0..self.log {
+            let bv = &self.levels[d];
+            let (l0, r0) = (bv.rank0(l), bv.rank0(r));
+            if k < r0 - l0 {
+                l = l0;
+                r = r0;
+            } else {
+                k -= r0 - l0;
+                res |= 1 << (self.log - 1 - d);
+                l = self.mids[d] + (l - l0);
+                r = self.mids[d] + (r - r0);
+            }
+        }
+        res
+    }
+
+    /// Returns the `k`-th (0-indexed) largest value in `a[l..r]`.
+    pub fn kth_largest(
+        &self,
+        l: usize,
+        r: usize,
+        k: usize,
+    ) -> u64 {
+        assert!(l <= r && k < r - l, "k is out of range");
+        self.kth_smallest(l, r, r - l - 1 - k)
+    }
+
+    /// Returns the number of values less than `upper` in `a[l..r]`.
+    pub fn count_less(
+        &self,
+        mut l: usize,
+        mut r: usize,
+        upper: u64,
+    ) -> usize {
+        assert!(l <= r && r <= self.n, "Invalid range");
+        if upper.checked_shr(self.log as u32).unwrap_or(0) != 0 {
+            return r - l;
+        }
+        let mut res = 0;
+        for d in 0..self.log {
+            let bv = &self.levels[d];
+            let (l0, r0) = (bv.rank0(l), bv.rank0(r));
+            if (upper >> (self.log - 1 - d)) & 1 == 1 {
+                res += r0 - l0;
+                l = self.mids[d] + (l - l0);
+                r = self.mids[d] + (r - r0);
+            } else {
+                l = l0;
+                r = r0;
+            }
+        }
+        res
+    }
+
+    /// Returns the number of values in `[lower, upper)` in `a[l..r]`.
+    pub fn range_freq(
+        &self,
+        l: usize,
+        r: usize,
+        lower: u64,
+        upper: u64,
+    ) -> usize {
+        if lower >= upper {
+            return 0;
+        }
+        self.count_less(l, r, upper) - self.count_less(l, r, lower)
+    }
+
+    /// Returns the largest value less than `upper` in `a[l..r]`.
+    pub fn prev_value(
+        &self,
+        l: usize,
+        r: usize,
+        upper: u64,
+    ) -> Option<u64> {
+        let cnt = self.count_less(l, r, upper);
+        if cnt == 0 {
+            None
+        } else {
+            Some(self.kth_smallest(l, r, cnt - 1))
+        }
+    }
+
+    /// Returns the smallest value greater than or equal to `lower` in `a[l..r]`.
+    pub fn next_value(
+        &self,
+        l: usize,
+        r: usize,
+        lower: u64,
+    ) -> Option<u64> {
+        let cnt = self.count_less(l, r, lower);
+        if cnt == r - l {
+            None
+        } else {
+            Some(self.kth_smallest(l, r, cnt))
+        }
+    }
+
+    /// Returns the sum of values less than `upper` in `a[l..r]`.
+    ///
+    /// # Panics
+    /// Panics if the matrix was not built with `with_sum`.
+    pub fn sum_below(
+        &self,
+        mut l: usize,
+        mut r: usize,
+        upper: u64,
+    ) -> u64 {
+        assert!(l <= r && r <= self.n, "Invalid range");
+        let sums = self.sums.as_ref().expect("Build with WaveletMatrix::with_sum");
+        if upper.checked_shr(self.log as u32).unwrap_or(0) != 0 {
+            return sums[0][r] - sums[0][l];
+        }
+        let mut res = 0;
+        for d in 0..self.log {
+            let bv = &self.levels[d];
+            let (l0, r0) = (bv.rank0(l), bv.rank0(r));
+            if (upper >> (self.log - 1 - d)) & 1 == 1 {
+                // The zero side at depth d + 1 occupies [l0, r0)
+                res += sums[d + 1][r0] - sums[d + 1][l0];
+                l = self.mids[d] + (l - l0);
+                r = self.mids[d] + (r - r0);
+            } else {
+                l = l0;
+                r = r0;
+            }
+        }
+        res
+    }
+}
+
+// --- SNAP END ---
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wavelet_matrix_random() {
+        let mut seed: u64 = 0x1234_5678_9ABC_DEF0;
+        let mut next = |m: u64| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed % m
+        };
+        for &max in &[1u64, 2, 16, 1000, 1 << 40] {
+            let n = 40;
+            let a: Vec<u64> = (0..n).map(|_| next(max)).collect();
+            let wm = WaveletMatrix::with_sum(&a);
+            for l in 0..=n {
+                for r in l..=n {
+                    let mut sorted = a[l..r].to_vec();
+                    sorted.sort();
+                    for (k, &x) in sorted.iter().enumerate() {
+                        assert_eq!(wm.kth_smallest(l, r, k), x);
+                    }
+                    for _ in 0..3 {
+                        let x = next(max + 2);
+                        let y = next(max + 2);
+                        let below: Vec<u64> = sorted.iter().copied().filter(|&v| v < x).collect();
+                        assert_eq!(wm.count_less(l, r, x), below.len());
+                        assert_eq!(wm.sum_below(l, r, x), below.iter().sum::<u64>());
+                        assert_eq!(wm.range_freq(l, r, x, y), sorted.iter().filter(|&&v| x <= v && v < y).count());
+                        assert_eq!(wm.prev_value(l, r, x), below.last().copied());
+                        assert_eq!(wm.next_value(l, r, x), sorted.iter().copied().find(|&v| v >= x));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_wavelet_matrix_large_values() {
+        let a = vec![u64::MAX, 0, u64::MAX - 1, 1 << 63];
+        let wm = WaveletMatrix::new(&a);
+        assert_eq!(wm.kth_smallest(0, 4, 3), u64::MAX);
+        assert_eq!(wm.kth_largest(0, 4, 1), u64::MAX - 1);
+        assert_eq!(wm.count_less(0, 4, u64::MAX), 3);
+        assert_eq!(wm.next_value(1, 4, 1), Some(1 << 63));
+    }
+}
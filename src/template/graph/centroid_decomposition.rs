@@ -0,0 +1,270 @@
+#![allow(dead_code)]
+
+// INJECT: src/template/graph/tree.rs
+
+// --- SNAP START ---
+
+/// Centroid Decomposition
+///
+/// Recursively removes a centroid of every component, which builds the centroid tree:
+/// its depth is O(log N), and every path of the original tree passes through
+/// the highest (smallest `level`) centroid among its vertices.
+///
+/// `for_each_component` visits the component of every centroid with distances from it,
+/// which is the core of problems like "number of pairs at distance k".
+/// `ancestor_dists` gives the distance from every vertex to each of its centroid ancestors,
+/// which answers "nearest marked vertex" queries in O(log N) each.
+///
+/// # Examples
+///
+/// ```
+/// let mut tree = Tree::new(5);
+/// for v in 1..5 {
+///     tree.add_edge(v - 1, v, 1); // path 0 - 1 - 2 - 3 - 4
+/// }
+/// let cd = CentroidDecomposition::new(&tree);
+/// assert_eq!(cd.root(), 2);
+/// assert_eq!(cd.level(2), 0);
+/// assert_eq!(cd.parent(0), Some(1));
+///
+/// // Number of pairs at distance 2: each pair is counted at the centroid separating it
+/// let mut count = 0;
+/// cd.for_each_component(&tree, |_, comp| {
+///     for &(u, du, bu) in comp {
+///         for &(v, dv, bv) in comp {
+///             if u < v && bu != bv && du + dv == 2 {
+///                 count += 1;
+///             }
+///         }
+///     }
+/// });
+/// assert_eq!(count, 3);
+/// ```
+///
+/// # Complexity
+/// - Construction: O(N log N)
+/// - `for_each_component`, `ancestor_dists`: O(N log N) in total
+#[derive(Debug, Clone)]
+pub struct CentroidDecomposition {
+    root: usize,
+    parent: Vec<Option<usize>>,
+    level: Vec<usize>,
+    // Centroids in the order they were removed (parents before children)
+    order: Vec<usize>,
+}
+
+impl CentroidDecomposition {
+    /// Decomposes `tree` (which must be connected).
+    pub fn new(tree: &Tree) -> Self {
+        let n = tree.len();
+        let mut parent = vec![None; n];
+        let mut level = vec![0; n];
+        let mut removed = vec![false; n];
+        let mut order = Vec::with_capacity(n);
+        let mut size = vec![0; n];
+        let mut par = vec![usize::MAX; n];
+
+        // (a vertex of the component, parent centroid)
+        let mut tasks = if n > 0 { vec![(0, None)] } else { vec![] };
+        while let Some((start, p)) = tasks.pop() {
+            // Iterative DFS over the component
+            let mut visit = vec![start];
+            par[start] = usize::MAX;
+            let mut i = 0;
+            while i < visit.len() {
+                let u = visit[i];
+                i += 1;
+                for &(v, _) in tree.neighbors(u) {
+                    if v != par[u] && !removed[v] {
+                        par[v] = u;
+                        visit.push(v);
+                    }
+                }
+            }
+            for &u in visit.iter().rev() {
+                size[u] = 1 + tree
+                    .neighbors(u)
+                    .iter()
+                    .filter(|&&(v, _)| v != par[u] && !removed[v])
+                    .map(|&(v, _)| size[v])
+                    .sum::<usize>();
+            }
+
+            // The centroid: the deepest vertex whose subtree has more than half of the component
+            let total = visit.len();
+            let c = *visit.iter().rev().find(|&&u| 2 * size[u] > total).unwrap();
+
+            removed[c] = true;
+            parent[c] = p;
+            level[c] = p.map_or(0, |p: usize| level[p] + 1);
+            order.push(c);
+            for &(v, _) in tree.neighbors(c) {
+                if !removed[v] {
+                    tasks.push((v, Some(c)));
+                }
+            }
+        }
+
+        CentroidDecomposition {
+            root: order.first().copied().unwrap_or(0),
+            parent,
+            level,
+            order,
+        }
+    }
+
+    /// Returns the root of the centroid tree.
+    pub fn root(&self) -> usize {
+        self.root
+    }
+
+    /// Returns the parent of `v` in the centroid tree.
+    pub fn parent(
+        &self,
+        v: usize,
+    ) -> Option<usize> {
+        self.parent[v]
+    }
+
+    /// Returns the depth of `v` in the centroid tree (the root has level 0).
+    pub fn level(
+        &self,
+        v: usize,
+    ) -> usize {
+        self.level[v]
+    }
+
+    /// Returns the centroids in the order they were removed (every parent comes before its children).
+    pub fn order(&self) -> &[usize] {
+        &self.order
+    }
+
+    /// Returns the component of `c` at the time it was removed, as `(vertex, dist, branch)`:
+    /// `dist` is the weighted distance from `c`, and `branch` is the neighbor of `c` the vertex hangs from
+    /// (`c` itself for `c`). Vertices in the same branch are connected without passing through `c`.
+    pub fn component(
+        &self,
+        tree: &Tree,
+        c: usize,
+    ) -> Vec<(usize, i64, usize)> {
+        let mut res = vec![(c, 0, c)];
+        let mut from = vec![c];
+        let mut i = 0;
+        while i < res.len() {
+            let (u, d, b) = res[i];
+            let p = from[i];
+            i += 1;
+            for &(v, w) in tree.neighbors(u) {
+                if v != p && v != c && self.level[v] > self.level[c] {
+                    res.push((v, d + w, if u == c { v } else { b }));
+                    from.push(u);
+                }
+            }
+        }
+        res
+    }
+
+    /// Calls `f(c, component(c))` for every centroid `c`, parents before children.
+    pub fn for_each_component(
+        &self,
+        tree: &Tree,
+        mut f: impl FnMut(usize, &[(usize, i64, usize)]),
+    ) {
+        for &c in &self.order {
+            let comp = self.component(tree, c);
+            f(c, &comp);
+        }
+    }
+
+    /// Returns, for every vertex `v`, the list of `(ancestor, dist(v, ancestor))`
+    /// over its centroid ancestors including `v` itself, from the root down (index = level).
+    pub fn ancestor_dists(
+        &self,
+        tree: &Tree,
+    ) -> Vec<Vec<(usize, i64)>> {
+        let mut res = vec![Vec::new(); self.level.len()];
+        self.for_each_component(tree, |c, comp| {
+            for &(v, d, _) in comp {
+                res[v].push((c, d));
+            }
+        });
+        res
+    }
+}
+
+// --- SNAP END ---
+
+#[allow(unused_imports)]
+use super::tree::Tree;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_centroid_decomposition() {
+        let mut seed: u64 = 0x1F83_D9AB_FB41_BD6B;
+        let mut next = |m: u64| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed % m
+        };
+        for n in [1, 2, 7, 64, 150] {
+            let mut tree = Tree::new(n);
+            for v in 1..n {
+                // Mix of long paths and random attachments
+                let p = if next(2) == 0 { v - 1 } else { next(v as u64) as usize };
+                tree.add_edge(p, v, next(5) as i64 + 1);
+            }
+            let cd = CentroidDecomposition::new(&tree);
+            assert_eq!(cd.order().len(), n);
+
+            // Brute force distances
+            let mut dist = vec![vec![-1i64; n]; n];
+            for (s, row) in dist.iter_mut().enumerate() {
+                let mut stack = vec![s];
+                row[s] = 0;
+                while let Some(u) = stack.pop() {
+                    for &(v, w) in tree.neighbors(u) {
+                        if row[v] < 0 {
+                            row[v] = row[u] + w;
+                            stack.push(v);
+                        }
+                    }
+                }
+            }
+
+            let mut covered = 0;
+            cd.for_each_component(&tree, |c, comp| {
+                covered += 1;
+                assert!(comp.len() <= n >> cd.level(c));
+                for &(v, d, _) in comp {
+                    assert_eq!(d, dist[c][v]);
+                    assert!(cd.level(v) >= cd.level(c));
+                }
+                // Vertices in different branches are separated by c
+                for &(u, du, bu) in comp {
+                    for &(v, dv, bv) in comp {
+                        if bu != bv {
+                            assert_eq!(du + dv, dist[u][v]);
+                        }
+                    }
+                }
+            });
+            assert_eq!(covered, n);
+
+            let anc = cd.ancestor_dists(&tree);
+            for v in 0..n {
+                assert_eq!(anc[v].len(), cd.level(v) + 1);
+                let mut x = Some(v);
+                for &(a, d) in anc[v].iter().rev() {
+                    assert_eq!(Some(a), x);
+                    assert_eq!(d, dist[a][v]);
+                    x = cd.parent(a);
+                }
+                assert_eq!(x, None);
+            }
+        }
+    }
+}
@@ -0,0 +1,383 @@
+#![allow(dead_code)]
+
+// INJECT: src/template/graph/tree.rs
+
+// --- SNAP START ---
+
+/// Heavy-Light Decomposition (Path / Subtree Queries on a Tree)
+///
+/// Assigns every vertex a position in `[0, N)` such that
+/// - every path is covered by O(log N) contiguous ranges (`path_ranges`), and
+/// - every subtree is one contiguous range (`subtree_range`).
+///
+/// Build a `SegmentTree` / `LazySegmentTree` over `order()` and query the returned ranges
+/// (e.g. path-add is `apply(l, r, f)` on every range of `path_ranges`).
+///
+/// - Vertex-weighted: the value of `v` is stored at `pos(v)`.
+/// - Edge-weighted (`edge = true`): the value of the edge `(parent(v), v)` is stored at `pos(v)`;
+///   use `edge_pos(u, v)` to locate an edge.
+///
+/// # Examples
+///
+/// ```
+/// let mut tree = Tree::new(5);
+/// tree.add_edge(0, 1, 1);
+/// tree.add_edge(1, 2, 1);
+/// tree.add_edge(1, 3, 1);
+/// tree.add_edge(0, 4, 1);
+/// let hld = HeavyLightDecomposition::new(&tree, 0);
+///
+/// // Path sum with a segment tree over the HLD order
+/// let a = vec![1i64, 10, 100, 1000, 10000];
+/// let data: Vec<i64> = hld.order().iter().map(|&v| a[v]).collect();
+/// let mut st = SegmentTree::new(&data, |x, y| x + y, 0);
+/// let path_sum = |st: &SegmentTree<i64, _>, u, v| {
+///     hld.path_ranges(u, v, false).iter().map(|&(l, r, _)| st.query(l, r)).sum::<i64>()
+/// };
+/// assert_eq!(path_sum(&st, 2, 4), 10111);
+///
+/// st.update(hld.pos(1), 20);
+/// assert_eq!(path_sum(&st, 2, 3), 1120);
+///
+/// // Subtree sum
+/// let (l, r) = hld.subtree_range(1);
+/// assert_eq!(st.query(l, r), 1120);
+/// ```
+///
+/// # Complexity
+/// - Construction: O(N)
+/// - `path_ranges`, `lca`: O(log N)
+#[derive(Debug, Clone)]
+pub struct HeavyLightDecomposition {
+    n: usize,
+    parent: Vec<usize>,
+    depth: Vec<usize>,
+    head: Vec<usize>,
+    pos: Vec<usize>,
+    end: Vec<usize>,
+    order: Vec<usize>,
+}
+
+impl HeavyLightDecomposition {
+    /// Decomposes `tree` rooted at `root`.
+    pub fn new(
+        tree: &Tree,
+        root: usize,
+    ) -> Self {
+        let n = tree.len();
+        let mut parent = vec![n; n];
+        let mut depth = vec![0; n];
+        let mut visit = Vec::with_capacity(n);
+
+        // Iterative DFS for parents and a pre-order
+        let mut stack = vec![root];
+        parent[root] = root;
+        while let Some(u) = stack.pop() {
+            visit.push(u);
+            for &(v, _) in tree.neighbors(u) {
+                if v != parent[u] {
+                    parent[v] = u;
+                    depth[v] = depth[u] + 1;
+                    stack.push(v);
+                }
+            }
+        }
+
+        // Subtree sizes and the heavy child of each vertex
+        let mut size = vec![1; n];
+        let mut heavy = vec![n; n];
+        for &u in visit.iter().rev() {
+            if u != root {
+                size[parent[u]] += size[u];
+            }
+        }
+        for &u in &visit {
+            heavy[u] = tree
+                .neighbors(u)
+                .iter()
+                .map(|&(v, _)| v)
+                .filter(|&v| v != parent[u])
+                .max_by_key(|&v| size[v])
+                .unwrap_or(n);
+        }
+
+        // Assign positions so that each heavy path is contiguous
+        let mut head = vec![root; n];
+        let mut pos = vec![0; n];
+        let mut order = Vec::with_capacity(n);
+        let mut stack = vec![root];
+        while let Some(u) = stack.pop() {
+            pos[u] = order.len();
+            order.push(u);
+            for &(v, _) in tree.neighbors(u) {
+                if v != parent[u] && v != heavy[u] {
+                    head[v] = v;
+                    stack.push(v);
+                }
+            }
+            // Pushed last so that it is visited right after `u`
+            if heavy[u] != n {
+                head[heavy[u]] = head[u];
+                stack.push(heavy[u]);
+            }
+        }
+        let end = (0..n).map(|v| pos[v] + size[v]).collect();
+
+        HeavyLightDecomposition {
+            n,
+            parent,
+            depth,
+            head,
+            pos,
+            end,
+            order,
+        }
+    }
+
+    /// Returns the position of `v`.
+    pub fn pos(
+        &self,
+        v: usize,
+    ) -> usize {
+        self.pos[v]
+    }
+
+    /// Returns the vertices in position order (`order()[pos(v)] == v`).
+    pub fn order(&self) -> &[usize] {
+        &self.order
+    }
+
+    /// Returns the parent of `v` (the root is its own parent).
+    pub fn parent(
+        &self,
+        v: usize,
+    ) -> usize {
+        self.parent[v]
+    }
+
+    pub fn depth(
+        &self,
+        v: usize,
+    ) -> usize {
+        self.depth[v]
+    }
+
+    /// Returns the position storing the edge `(u, v)` in the edge-weighted setting.
+    ///
+    /// # Panics
+    /// Panics if `u` and `v` are not adjacent.
+    pub fn edge_pos(
+        &self,
+        u: usize,
+        v: usize,
+    ) -> usize {
+        if self.parent[v] == u && v != u {
+            self.pos[v]
+        } else {
+            assert!(self.parent[u] == v && u != v, "Not an edge");
+            self.pos[u]
+        }
+    }
+
+    /// Returns the range `[l, r)` of positions of the subtree of `v`.
+    pub fn subtree_range(
+        &self,
+        v: usize,
+    ) -> (usize, usize) {
+        (self.pos[v], self.end[v])
+    }
+
+    pub fn lca(
+        &self,
+        mut u: usize,
+        mut v: usize,
+    ) -> usize {
+        while self.head[u] != self.head[v] {
+            if self.depth[self.head[u]] > self.depth[self.head[v]] {
+                u = self.parent[self.head[u]];
+            } else {
+                v = self.parent[self.head[v]];
+            }
+        }
+        if self.depth[u] < self.depth[v] { u } else { v }
+    }
+
+    /// Returns the position ranges covering the path from `u` to `v`, in the order the path visits them.
+    ///
+    /// Each item is `(l, r, reversed)`: when `reversed` is true, the path walks `[l, r)` from `r - 1` down to `l`
+    /// (towards the root), so a non-commutative product must be taken in reverse.
+    /// With `edge = true`, the LCA is excluded (edge-weighted setting).
+    pub fn path_ranges(
+        &self,
+        mut u: usize,
+        mut v: usize,
+        edge: bool,
+    ) -> Vec<(usize, usize, bool)> {
+        let mut up = Vec::new();
+        let mut down = Vec::new();
+        while self.head[u] != self.head[v] {
+            if self.depth[self.head[u]] >= self.depth[self.head[v]] {
+                up.push((self.pos[self.head[u]], self.pos[u] + 1, true));
+                u = self.parent[self.head[u]];
+            } else {
+                down.push((self.pos[self.head[v]], self.pos[v] + 1, false));
+                v = self.parent[self.head[v]];
+            }
+        }
+        let e = edge as usize;
+        if self.pos[u] >= self.pos[v] {
+            if self.pos[v] + e <= self.pos[u] {
+                up.push((self.pos[v] + e, self.pos[u] + 1, true));
+            }
+        } else {
+            down.push((self.pos[u] + e, self.pos[v] + 1, false));
+        }
+        up.extend(down.into_iter().rev());
+        up
+    }
+
+    /// Folds the path from `u` to `v` in path order.
+    ///
+    /// `prod(l, r, reversed)` must return the product over positions `[l, r)`,
+    /// taken from `r - 1` down to `l` when `reversed` is true.
+    /// For a commutative operation `reversed` can be ignored; otherwise keep a second segment tree with the flipped operation.
+    pub fn fold_path<T>(
+        &self,
+        u: usize,
+        v: usize,
+        edge: bool,
+        identity: T,
+        op: impl Fn(T, T) -> T,
+        mut prod: impl FnMut(usize, usize, bool) -> T,
+    ) -> T {
+        self.path_ranges(u, v, edge)
+            .into_iter()
+            .fold(identity, |acc, (l, r, rev)| op(acc, prod(l, r, rev)))
+    }
+
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+}
+
+// --- SNAP END ---
+
+#[allow(unused_imports)]
+use super::tree::Tree;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn random_tree(
+        n: usize,
+        seed: &mut u64,
+    ) -> (Tree, Vec<usize>) {
+        let mut next = |m: u64| {
+            *seed ^= *seed << 13;
+            *seed ^= *seed >> 7;
+            *seed ^= *seed << 17;
+            *seed % m
+        };
+        let mut tree = Tree::new(n);
+        let parent: Vec<usize> = (0..n).map(|v| if v == 0 { 0 } else { next(v as u64) as usize }).collect();
+        for (v, &p) in parent.iter().enumerate().skip(1) {
+            tree.add_edge(p, v, 1);
+        }
+        (tree, parent)
+    }
+
+    fn naive_path(
+        parent: &[usize],
+        mut u: usize,
+        mut v: usize,
+    ) -> Vec<usize> {
+        let depth = |mut x: usize| {
+            let mut d = 0;
+            while x != 0 {
+                x = parent[x];
+                d += 1;
+            }
+            d
+        };
+        let (mut front, mut back) = (vec![], vec![]);
+        while u != v {
+            if depth(u) >= depth(v) {
+                front.push(u);
+                u = parent[u];
+            } else {
+                back.push(v);
+                v = parent[v];
+            }
+        }
+        front.push(u);
+        front.extend(back.into_iter().rev());
+        front
+    }
+
+    #[test]
+    fn test_hld_path_order() {
+        let mut seed = 0x510E_527F_ADE6_82D1;
+        let n = 60;
+        let (tree, parent) = random_tree(n, &mut seed);
+        let hld = HeavyLightDecomposition::new(&tree, 0);
+        for u in 0..n {
+            for v in 0..n {
+                let path = naive_path(&parent, u, v);
+                // Vertex order along the path, reconstructed from the ranges
+                let mut walked = Vec::new();
+                for (l, r, rev) in hld.path_ranges(u, v, false) {
+                    let mut seg: Vec<usize> = hld.order()[l..r].to_vec();
+                    if rev {
+                        seg.reverse();
+                    }
+                    walked.extend(seg);
+                }
+                assert_eq!(walked, path);
+                assert_eq!(hld.lca(u, v), *path.iter().min_by_key(|&&x| hld.depth(x)).unwrap());
+
+                // Edge version: every vertex except the LCA, i.e. the deeper endpoint of each edge
+                let mut edges: Vec<usize> = hld
+                    .path_ranges(u, v, true)
+                    .iter()
+                    .flat_map(|&(l, r, _)| hld.order()[l..r].to_vec())
+                    .collect();
+                edges.sort();
+                let mut expected: Vec<usize> = path.iter().copied().filter(|&x| x != hld.lca(u, v)).collect();
+                expected.sort();
+                assert_eq!(edges, expected);
+            }
+            let (l, r) = hld.subtree_range(u);
+            for &x in &hld.order()[l..r] {
+                assert_eq!(hld.lca(u, x), u);
+            }
+        }
+    }
+
+    #[test]
+    fn test_hld_fold_non_commutative() {
+        let mut seed = 0x9B05_688C_2B3E_6C1F;
+        let n = 40;
+        let (tree, parent) = random_tree(n, &mut seed);
+        let hld = HeavyLightDecomposition::new(&tree, 0);
+        // String concatenation along the path
+        let name = |v: usize| format!("{},", v);
+        for u in 0..n {
+            for v in 0..n {
+                let res = hld.fold_path(u, v, false, String::new(), |a, b| a + &b, |l, r, rev| {
+                    let mut vs: Vec<usize> = hld.order()[l..r].to_vec();
+                    if rev {
+                        vs.reverse();
+                    }
+                    vs.into_iter().map(name).collect()
+                });
+                let expected: String = naive_path(&parent, u, v).into_iter().map(name).collect();
+                assert_eq!(res, expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,476 @@
+#![allow(dead_code)]
+// --- SNAP START ---
+
+/// Lowlink on Undirected Multigraphs
+///
+/// Bridges, articulation points, two-edge-connected components (with the bridge tree)
+/// and biconnected components (with the block-cut tree).
+///
+/// Edges are identified by the id returned from `add_edge`, so parallel edges are handled correctly
+/// (two parallel edges are never bridges). Self-loops are ignored.
+/// The graph does not have to be connected; the trees become forests.
+///
+/// # Examples
+///
+/// ```
+/// // 0 - 1 = 2 - 3, 3 - 4 - 5 - 3 (the edge 1 = 2 is doubled)
+/// let mut g = LowLinkGraph::new(6);
+/// let e01 = g.add_edge(0, 1);
+/// g.add_edge(1, 2);
+/// g.add_edge(1, 2);
+/// let e23 = g.add_edge(2, 3);
+/// g.add_edge(3, 4);
+/// g.add_edge(4, 5);
+/// g.add_edge(5, 3);
+///
+/// let ll = g.lowlink();
+/// assert_eq!(ll.bridges, vec![e01, e23]);
+/// assert_eq!(ll.articulation_points, vec![1, 2, 3]);
+///
+/// let tecc = g.two_edge_connected_components();
+/// assert_eq!(tecc.groups.len(), 3); // {0}, {1, 2}, {3, 4, 5}
+/// assert_eq!(tecc.ids[1], tecc.ids[2]);
+///
+/// let bcc = g.biconnected_components();
+/// assert_eq!(bcc.blocks.len(), 4); // {0, 1}, {1, 2}, {2, 3}, {3, 4, 5}
+/// // Block-cut tree: [0, 1] - (1) - [1, 2] - (2) - [2, 3] - (3) - [3, 4, 5]
+/// assert_eq!(bcc.tree.len(), 7);
+/// assert_eq!(bcc.tree[bcc.node[1]].len(), 2);
+/// ```
+///
+/// # Complexity
+/// - O(N + M) for every method
+pub struct LowLinkGraph {
+    n: usize,
+    pub edges: Vec<(usize, usize)>,
+}
+
+pub struct LowLinkResult {
+    /// DFS pre-order index of each vertex
+    pub ord: Vec<usize>,
+    /// smallest `ord` reachable with at most one back edge from the DFS subtree
+    pub low: Vec<usize>,
+    /// ids of the bridges in increasing order
+    pub bridges: Vec<usize>,
+    /// articulation points in increasing order
+    pub articulation_points: Vec<usize>,
+}
+
+pub struct TwoEdgeCcResult {
+    /// component ID for each vertex
+    pub ids: Vec<usize>,
+    /// vertices belonging to each component
+    pub groups: Vec<Vec<usize>>,
+    /// bridge tree: `(component, bridge id)` for each component
+    pub tree: Vec<Vec<(usize, usize)>>,
+}
+
+pub struct BccResult {
+    /// vertices belonging to each block (an isolated vertex forms a block by itself)
+    pub blocks: Vec<Vec<usize>>,
+    /// edge ids belonging to each block
+    pub block_edges: Vec<Vec<usize>>,
+    /// node of the block-cut tree for each vertex:
+    /// `blocks.len() + k` for the k-th articulation point, otherwise its block
+    pub node: Vec<usize>,
+    /// block-cut tree: nodes `0..blocks.len()` are blocks, the rest are articulation points
+    pub tree: Vec<Vec<usize>>,
+}
+
+// Raw output of the DFS shared by all methods
+struct Dfs {
+    ord: Vec<usize>,
+    low: Vec<usize>,
+    parent_edge: Vec<usize>,
+    preorder: Vec<usize>,
+    blocks: Vec<Vec<usize>>,
+}
+
+impl LowLinkGraph {
+    pub fn new(n: usize) -> Self {
+        Self {
+            n,
+            edges: Vec::new(),
+        }
+    }
+
+    /// Adds an undirected edge and returns its id.
+    pub fn add_edge(
+        &mut self,
+        u: usize,
+        v: usize,
+    ) -> usize {
+        self.edges.push((u, v));
+        self.edges.len() - 1
+    }
+
+    pub fn lowlink(&self) -> LowLinkResult {
+        let dfs = self.dfs();
+        let bridges = self.bridges(&dfs);
+
+        let mut is_art = vec![false; self.n];
+        let mut root_children = vec![0; self.n];
+        for &v in &dfs.preorder {
+            let e = dfs.parent_edge[v];
+            if e == !0 {
+                continue;
+            }
+            let p = self.other(e, v);
+            if dfs.parent_edge[p] == !0 {
+                root_children[p] += 1;
+            } else if dfs.low[v] >= dfs.ord[p] {
+                is_art[p] = true;
+            }
+        }
+        let articulation_points = (0..self.n)
+            .filter(|&v| is_art[v] || root_children[v] >= 2)
+            .collect();
+
+        LowLinkResult {
+            ord: dfs.ord,
+            low: dfs.low,
+            bridges,
+            articulation_points,
+        }
+    }
+
+    pub fn two_edge_connected_components(&self) -> TwoEdgeCcResult {
+        let dfs = self.dfs();
+        let bridges = self.bridges(&dfs);
+        let mut is_bridge = vec![false; self.edges.len()];
+        for &e in &bridges {
+            is_bridge[e] = true;
+        }
+
+        // A vertex starts a new component iff it is a DFS root or entered through a bridge
+        let mut ids = vec![0; self.n];
+        let mut group_num = 0;
+        for &v in &dfs.preorder {
+            let e = dfs.parent_edge[v];
+            if e == !0 || is_bridge[e] {
+                ids[v] = group_num;
+                group_num += 1;
+            } else {
+                ids[v] = ids[self.other(e, v)];
+            }
+        }
+
+        let mut groups = vec![Vec::new(); group_num];
+        for v in 0..self.n {
+            groups[ids[v]].push(v);
+        }
+        let mut tree = vec![Vec::new(); group_num];
+        for e in bridges {
+            let (u, v) = self.edges[e];
+            tree[ids[u]].push((ids[v], e));
+            tree[ids[v]].push((ids[u], e));
+        }
+
+        TwoEdgeCcResult { ids, groups, tree }
+    }
+
+    pub fn biconnected_components(&self) -> BccResult {
+        let dfs = self.dfs();
+        let mut block_edges = dfs.blocks;
+        let mut blocks = Vec::with_capacity(block_edges.len());
+        let mut last_block = vec![!0; self.n];
+        let mut block_count = vec![0; self.n];
+        for (b, es) in block_edges.iter().enumerate() {
+            let mut vs = Vec::new();
+            for &e in es {
+                let (u, v) = self.edges[e];
+                for x in [u, v] {
+                    if last_block[x] != b {
+                        last_block[x] = b;
+                        block_count[x] += 1;
+                        vs.push(x);
+                    }
+                }
+            }
+            vs.sort_unstable();
+            blocks.push(vs);
+        }
+        for v in 0..self.n {
+            if block_count[v] == 0 {
+                last_block[v] = blocks.len();
+                block_count[v] = 1;
+                blocks.push(vec![v]);
+                block_edges.push(Vec::new());
+            }
+        }
+
+        // Articulation points are exactly the vertices in two or more blocks
+        let mut node = last_block;
+        let mut size = blocks.len();
+        for v in 0..self.n {
+            if block_count[v] >= 2 {
+                node[v] = size;
+                size += 1;
+            }
+        }
+        let mut tree = vec![Vec::new(); size];
+        for (b, vs) in blocks.iter().enumerate() {
+            for &v in vs {
+                if block_count[v] >= 2 {
+                    tree[b].push(node[v]);
+                    tree[node[v]].push(b);
+                }
+            }
+        }
+
+        BccResult {
+            blocks,
+            block_edges,
+            node,
+            tree,
+        }
+    }
+
+    fn other(
+        &self,
+        e: usize,
+        v: usize,
+    ) -> usize {
+        let (a, b) = self.edges[e];
+        a ^ b ^ v
+    }
+
+    fn bridges(
+        &self,
+        dfs: &Dfs,
+    ) -> Vec<usize> {
+        let mut bridges: Vec<usize> = dfs
+            .preorder
+            .iter()
+            .filter(|&&v| dfs.parent_edge[v] != !0)
+            .filter(|&&v| dfs.low[v] > dfs.ord[self.other(dfs.parent_edge[v], v)])
+            .map(|&v| dfs.parent_edge[v])
+            .collect();
+        bridges.sort_unstable();
+        bridges
+    }
+
+    fn dfs(&self) -> Dfs {
+        let mut start = vec![0; self.n + 1];
+        for &(u, v) in &self.edges {
+            if u != v {
+                start[u + 1] += 1;
+                start[v + 1] += 1;
+            }
+        }
+        for i in 0..self.n {
+            start[i + 1] += start[i];
+        }
+        let mut counter = start.clone();
+        let mut elist = vec![(0, 0); start[self.n]];
+        for (e, &(u, v)) in self.edges.iter().enumerate() {
+            if u != v {
+                elist[counter[u]] = (v, e);
+                counter[u] += 1;
+                elist[counter[v]] = (u, e);
+                counter[v] += 1;
+            }
+        }
+
+        let mut ord = vec![!0; self.n];
+        let mut low = vec![!0; self.n];
+        let mut parent_edge = vec![!0; self.n];
+        let mut preorder = Vec::with_capacity(self.n);
+        let mut blocks = Vec::new();
+        let mut edge_stack = Vec::new();
+        let mut stack = Vec::new();
+
+        for root in 0..self.n {
+            if ord[root] != !0 {
+                continue;
+            }
+            ord[root] = preorder.len();
+            low[root] = ord[root];
+            preorder.push(root);
+            stack.push((root, start[root]));
+
+            while let Some((curr, next_idx)) = stack.pop() {
+                if next_idx < start[curr + 1] {
+                    let (to, e) = elist[next_idx];
+                    stack.push((curr, next_idx + 1));
+                    if e == parent_edge[curr] {
+                        continue;
+                    }
+                    if ord[to] == !0 {
+                        ord[to] = preorder.len();
+                        low[to] = ord[to];
+                        parent_edge[to] = e;
+                        preorder.push(to);
+                        edge_stack.push(e);
+                        stack.push((to, start[to]));
+                    } else if ord[to] < ord[curr] {
+                        // Back edge (each one is seen first from its lower end)
+                        low[curr] = low[curr].min(ord[to]);
+                        edge_stack.push(e);
+                    }
+                } else if let Some(&(prev, _)) = stack.last() {
+                    low[prev] = low[prev].min(low[curr]);
+                    if low[curr] >= ord[prev] {
+                        // `prev` separates the block containing the tree edge to `curr`
+                        let e = parent_edge[curr];
+                        let mut block = Vec::new();
+                        while let Some(f) = edge_stack.pop() {
+                            block.push(f);
+                            if f == e {
+                                break;
+                            }
+                        }
+                        blocks.push(block);
+                    }
+                }
+            }
+        }
+
+        Dfs {
+            ord,
+            low,
+            parent_edge,
+            preorder,
+            blocks,
+        }
+    }
+}
+
+// --- SNAP END ---
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count_components(
+        n: usize,
+        edges: &[(usize, usize)],
+        skip_vertex: usize,
+        skip_edge: usize,
+    ) -> usize {
+        let mut adj = vec![vec![]; n];
+        for (e, &(u, v)) in edges.iter().enumerate() {
+            if e != skip_edge && u != skip_vertex && v != skip_vertex {
+                adj[u].push(v);
+                adj[v].push(u);
+            }
+        }
+        let mut seen = vec![false; n];
+        let mut count = 0;
+        for s in (0..n).filter(|&s| s != skip_vertex) {
+            if seen[s] {
+                continue;
+            }
+            count += 1;
+            seen[s] = true;
+            let mut stack = vec![s];
+            while let Some(u) = stack.pop() {
+                for &v in &adj[u] {
+                    if !seen[v] {
+                        seen[v] = true;
+                        stack.push(v);
+                    }
+                }
+            }
+        }
+        count
+    }
+
+    #[test]
+    fn test_lowlink_random() {
+        let mut seed: u64 = 0x6A09_E667_BB67_AE85;
+        let mut next = |m: u64| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed % m
+        };
+        for _ in 0..300 {
+            let n = next(9) as usize + 1;
+            let m = next(12) as usize;
+            let mut g = LowLinkGraph::new(n);
+            for _ in 0..m {
+                g.add_edge(next(n as u64) as usize, next(n as u64) as usize);
+            }
+            let edges = g.edges.clone();
+            let base = count_components(n, &edges, n, m);
+
+            let ll = g.lowlink();
+            let bridges: Vec<usize> = (0..m).filter(|&e| count_components(n, &edges, n, e) > base).collect();
+            assert_eq!(ll.bridges, bridges);
+            let arts: Vec<usize> = (0..n)
+                .filter(|&v| {
+                    let isolated = edges.iter().all(|&(a, b)| a != v && b != v);
+                    count_components(n, &edges, v, m) > base - isolated as usize
+                })
+                .collect();
+            assert_eq!(ll.articulation_points, arts);
+
+            // Two-edge-connected: connected after removing every bridge
+            let tecc = g.two_edge_connected_components();
+            let kept: Vec<(usize, usize)> = edges
+                .iter()
+                .enumerate()
+                .map(|(e, &uv)| if bridges.contains(&e) { (0, 0) } else { uv })
+                .collect();
+            assert_eq!(tecc.groups.len(), count_components(n, &kept, n, m));
+            for &(u, v) in &kept {
+                assert_eq!(tecc.ids[u], tecc.ids[v]);
+            }
+            let tree_edges: usize = tecc.tree.iter().map(|a| a.len()).sum();
+            assert_eq!(tree_edges, 2 * bridges.len());
+
+            // Biconnected: every non-loop edge in exactly one block, and the block-cut tree is a forest
+            let bcc = g.biconnected_components();
+            let mut owner = vec![None; m];
+            for (b, es) in bcc.block_edges.iter().enumerate() {
+                for &e in es {
+                    assert!(owner[e].is_none());
+                    owner[e] = Some(b);
+                    let (u, v) = edges[e];
+                    assert!(bcc.blocks[b].contains(&u) && bcc.blocks[b].contains(&v));
+                }
+            }
+            for (e, &(u, v)) in edges.iter().enumerate() {
+                assert_eq!(owner[e].is_some(), u != v);
+            }
+            // A block with at least two vertices stays connected after removing any of its vertices
+            for (b, vs) in bcc.blocks.iter().enumerate() {
+                let sub: Vec<(usize, usize)> = bcc.block_edges[b].iter().map(|&e| edges[e]).collect();
+                for &x in vs {
+                    let others = count_components(n, &sub, x, m) - (n - vs.len());
+                    assert!(vs.len() <= 2 || others == 1);
+                }
+            }
+            let nodes = bcc.tree.len();
+            let tree_edges: usize = bcc.tree.iter().map(|a| a.len()).sum::<usize>() / 2;
+            assert_eq!(nodes - tree_edges, base);
+            assert_eq!(nodes - bcc.blocks.len(), arts.len());
+            for v in 0..n {
+                let in_node = if bcc.node[v] < bcc.blocks.len() {
+                    bcc.blocks[bcc.node[v]].contains(&v)
+                } else {
+                    arts.contains(&v)
+                };
+                assert!(in_node);
+            }
+        }
+    }
+
+    #[test]
+    fn test_lowlink_deep() {
+        // A long path with one doubled edge in the middle
+        let n = 200_000;
+        let mut g = LowLinkGraph::new(n);
+        for v in 1..n {
+            g.add_edge(v - 1, v);
+        }
+        let extra = g.add_edge(n / 2, n / 2 + 1);
+        let ll = g.lowlink();
+        assert_eq!(ll.bridges.len(), n - 2);
+        assert!(!ll.bridges.contains(&extra));
+        assert_eq!(ll.articulation_points.len(), n - 2);
+        assert_eq!(g.two_edge_connected_components().groups.len(), n - 1);
+        assert_eq!(g.biconnected_components().blocks.len(), n - 1);
+    }
+}
@@ -1,6 +1,10 @@
+mod centroid_decomposition;
+mod hld;
+mod lowlink;
 mod max_flow;
 mod min_cost_flow;
 mod scc;
 mod tree;
+mod two_sat;
 mod unweighted;
 mod weighted;
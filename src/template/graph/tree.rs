@@ -60,6 +60,23 @@ impl Tree {
         }
     }
 
+    /// Returns the number of nodes.
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Returns the edges from `u` as `(to, cost)` pairs.
+    pub fn neighbors(
+        &self,
+        u: usize,
+    ) -> &[(usize, i64)] {
+        &self.edges[u]
+    }
+
     /// Adds an undirected edge between `u` and `v` with weight `w`.
     /// For unweighted trees, use `w = 1`.
     pub fn add_edge(
@@ -362,4 +379,324 @@ impl Tree {
 
         (in_time, out_time, euler, depth, dist)
     }
+
+    // ====================================================
+    // 4. Mo's Algorithm on Trees
+    // ====================================================
+
+    /// Converts path queries into range queries for Mo's algorithm (Mo on trees).
+    ///
+    /// Builds `tour` (length 2N) from `euler_tour`, in which every vertex appears once when entered and once when left.
+    /// For the path `u - v`, the vertices appearing exactly once in `tour[l..r]` are the vertices on the path,
+    /// except the LCA when it is returned as `extra`.
+    ///
+    /// Run `Mo` over `tour` with a single "toggle vertex `tour[i]`" callback for all four moves,
+    /// and in `answer` toggle `extra` on, read the answer and toggle it off again.
+    ///
+    /// # Panics
+    /// Panics if `build_lca` has not been called.
+    ///
+    /// # Returns
+    /// `(tour, ranges)` where `ranges[i] = (l, r, extra)` for `paths[i]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// // Number of distinct colors on a path
+    /// let mut tree = Tree::new(4);
+    /// tree.add_edge(0, 1, 1);
+    /// tree.add_edge(0, 2, 1);
+    /// tree.add_edge(2, 3, 1);
+    /// tree.build_lca(0);
+    /// let color = vec![0, 1, 1, 2];
+    /// let (tour, ranges) = tree.mo_path_ranges(&[(1, 3), (2, 3)]);
+    ///
+    /// let mut mo = Mo::new(tour.len());
+    /// for &(l, r, _) in &ranges {
+    ///     mo.add_query(l, r);
+    /// }
+    /// // (inside[v], count per color, distinct)
+    /// let mut state = (vec![false; 4], vec![0; 3], 0);
+    /// let toggle = |s: &mut (Vec<bool>, Vec<usize>, usize), v: usize| {
+    ///     s.0[v] = !s.0[v];
+    ///     if s.0[v] {
+    ///         s.1[color[v]] += 1;
+    ///         if s.1[color[v]] == 1 { s.2 += 1; }
+    ///     } else {
+    ///         s.1[color[v]] -= 1;
+    ///         if s.1[color[v]] == 0 { s.2 -= 1; }
+    ///     }
+    /// };
+    /// let step = |s: &mut _, i: usize| toggle(s, tour[i]);
+    /// let res = mo.solve(&mut state, step, step, step, step, |s, q| {
+    ///     let Some(w) = ranges[q].2 else { return s.2 };
+    ///     toggle(s, w);
+    ///     let ans = s.2;
+    ///     toggle(s, w);
+    ///     ans
+    /// });
+    /// assert_eq!(res, vec![3, 2]);
+    /// ```
+    ///
+    /// # Complexity
+    /// - O(N + Q log N)
+    pub fn mo_path_ranges(
+        &self,
+        paths: &[(usize, usize)],
+    ) -> (Vec<usize>, Vec<MoPathRange>) {
+        assert!(self.lca_ready, "LCA not built. Call build_lca(root) first.");
+        let (in_time, out_time, euler, _, _) = self.euler_tour();
+
+        // Expand the pre-order into enter/leave events
+        let mut tour = Vec::with_capacity(2 * self.n);
+        let mut first = vec![0; self.n];
+        let mut last = vec![0; self.n];
+        let mut open: Vec<usize> = Vec::new();
+        for &v in euler.iter().chain(std::iter::once(&self.n)) {
+            while let Some(&u) = open.last() {
+                if v != self.n && out_time[u] > in_time[v] {
+                    break;
+                }
+                open.pop();
+                last[u] = tour.len();
+                tour.push(u);
+            }
+            if v != self.n {
+                first[v] = tour.len();
+                tour.push(v);
+                open.push(v);
+            }
+        }
+
+        let ranges = paths
+            .iter()
+            .map(|&(u, v)| {
+                let (u, v) = if first[u] <= first[v] { (u, v) } else { (v, u) };
+                let w = self.lca(u, v);
+                if w == u {
+                    (first[u], first[v] + 1, None)
+                } else {
+                    (last[u], first[v] + 1, Some(w))
+                }
+            })
+            .collect();
+        (tour, ranges)
+    }
+
+    // ====================================================
+    // 5. Rerooting DP
+    // ====================================================
+
+    /// Computes a tree DP for every vertex as the root (全方位木DP).
+    ///
+    /// The DP value of `v` rooted at `v` is
+    /// `add_vertex(merge(add_edge(dp[c1], w1), add_edge(dp[c2], w2), ...), v)`
+    /// over the children `c` of `v` (`w` is the weight of the edge `(v, c)`).
+    /// `merge` must be commutative and associative with `identity` as its identity.
+    ///
+    /// Children are excluded with prefix/suffix folds, so `merge` needs no inverse.
+    ///
+    /// # Returns
+    /// A vector where `res[v]` is the DP value of the whole tree rooted at `v`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut tree = Tree::new(4);
+    /// tree.add_edge(0, 1, 1);
+    /// tree.add_edge(1, 2, 1);
+    /// tree.add_edge(1, 3, 1);
+    ///
+    /// // Sum of distances to all vertices: dp = (sum of distances from the root, number of vertices)
+    /// let res = tree.rerooting(
+    ///     (0i64, 0i64),
+    ///     |a, b| (a.0 + b.0, a.1 + b.1),
+    ///     |(s, c), w| (s + c * w, c),
+    ///     |(s, c), _| (s, c + 1),
+    /// );
+    /// let sum: Vec<i64> = res.iter().map(|&(s, _)| s).collect();
+    /// assert_eq!(sum, vec![5, 3, 5, 5]);
+    /// ```
+    ///
+    /// # Complexity
+    /// - O(N) calls of each function
+    pub fn rerooting<T: Clone>(
+        &self,
+        identity: T,
+        merge: impl Fn(T, T) -> T,
+        add_edge: impl Fn(T, i64) -> T,
+        add_vertex: impl Fn(T, usize) -> T,
+    ) -> Vec<T> {
+        if self.n == 0 {
+            return vec![];
+        }
+
+        // BFS order from 0 with parents
+        let mut order = Vec::with_capacity(self.n);
+        let mut parent = vec![usize::MAX; self.n];
+        order.push(0);
+        parent[0] = 0;
+        let mut i = 0;
+        while i < order.len() {
+            let u = order[i];
+            i += 1;
+            for &(v, _) in &self.edges[u] {
+                if parent[v] == usize::MAX {
+                    parent[v] = u;
+                    order.push(v);
+                }
+            }
+        }
+
+        // Bottom-up: dp of each subtree rooted at 0
+        let mut down: Vec<Option<T>> = vec![None; self.n];
+        for &u in order.iter().rev() {
+            let acc = self.edges[u]
+                .iter()
+                .filter(|&&(v, _)| parent[v] == u)
+                .fold(identity.clone(), |acc, &(v, w)| {
+                    merge(acc, add_edge(down[v].clone().unwrap(), w))
+                });
+            down[u] = Some(add_vertex(acc, u));
+        }
+
+        // Top-down: `up[v]` is the contribution of the parent side to `v`
+        let mut up: Vec<Option<T>> = vec![None; self.n];
+        let mut res: Vec<Option<T>> = vec![None; self.n];
+        for &u in &order {
+            let adj = &self.edges[u];
+            let contrib: Vec<T> = adj
+                .iter()
+                .map(|&(v, w)| {
+                    if u != 0 && v == parent[u] {
+                        up[u].clone().unwrap()
+                    } else {
+                        add_edge(down[v].clone().unwrap(), w)
+                    }
+                })
+                .collect();
+
+            // suffix[i] = merge of contrib[i..]
+            let mut suffix = vec![identity.clone(); adj.len() + 1];
+            for i in (0..adj.len()).rev() {
+                suffix[i] = merge(contrib[i].clone(), suffix[i + 1].clone());
+            }
+            let mut prefix = identity.clone();
+            for (i, &(v, w)) in adj.iter().enumerate() {
+                if u == 0 || v != parent[u] {
+                    let excluded = merge(prefix.clone(), suffix[i + 1].clone());
+                    up[v] = Some(add_edge(add_vertex(excluded, u), w));
+                }
+                prefix = merge(prefix, contrib[i].clone());
+            }
+            res[u] = Some(add_vertex(suffix[0].clone(), u));
+        }
+        res.into_iter().map(Option::unwrap).collect()
+    }
+}
+
+/// `(l, r, extra)` returned by `Tree::mo_path_ranges` for each path.
+pub type MoPathRange = (usize, usize, Option<usize>);
+
+// --- SNAP END ---
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mo_path_ranges() {
+        let mut seed: u64 = 0xA54F_F53A_5F1D_36F1;
+        let mut next = |m: u64| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed % m
+        };
+        let n = 30;
+        let mut tree = Tree::new(n);
+        let parent: Vec<usize> = (0..n).map(|v| if v == 0 { n } else { next(v as u64) as usize }).collect();
+        for (v, &p) in parent.iter().enumerate().skip(1) {
+            tree.add_edge(p, v, 1);
+        }
+        tree.build_lca(0);
+
+        let paths: Vec<(usize, usize)> = (0..200).map(|_| (next(n as u64) as usize, next(n as u64) as usize)).collect();
+        let (tour, ranges) = tree.mo_path_ranges(&paths);
+        assert_eq!(tour.len(), 2 * n);
+        for (&(u, v), &(l, r, extra)) in paths.iter().zip(&ranges) {
+            let mut inside = vec![false; n];
+            for &x in &tour[l..r] {
+                inside[x] = !inside[x];
+            }
+            if let Some(w) = extra {
+                assert!(!inside[w]);
+                inside[w] = true;
+            }
+            // Vertices on the path: ancestors of u or v up to the LCA
+            let w = tree.lca(u, v);
+            let mut expected = vec![false; n];
+            for mut x in [u, v] {
+                while x != w {
+                    expected[x] = true;
+                    x = parent[x];
+                }
+            }
+            expected[w] = true;
+            assert_eq!(inside, expected);
+        }
+    }
+
+    #[test]
+    fn test_rerooting() {
+        let mut seed: u64 = 0x3C6E_F372_FE94_F82B;
+        let mut next = |m: u64| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed % m
+        };
+        // (max distance from the root, size of the subtree, sum of labels weighted by depth)
+        type Dp = (i64, i64, i64);
+        let merge = |a: Dp, b: Dp| (a.0.max(b.0), a.1 + b.1, a.2 + b.2);
+        let add_edge = |a: Dp, w: i64| (a.0 + w, a.1, a.2 + a.1 * w);
+        let add_vertex = |a: Dp, v: usize| (a.0, a.1 + 1, a.2 + v as i64);
+
+        for n in [1, 2, 5, 40] {
+            let mut tree = Tree::new(n);
+            for v in 1..n {
+                tree.add_edge(next(v as u64) as usize, v, next(10) as i64 + 1);
+            }
+            let res = tree.rerooting((0, 0, 0), merge, add_edge, add_vertex);
+            for (root, &got) in res.iter().enumerate() {
+                let mut dist = vec![-1i64; n];
+                dist[root] = 0;
+                let mut stack = vec![root];
+                while let Some(u) = stack.pop() {
+                    for &(v, w) in tree.neighbors(u) {
+                        if dist[v] < 0 {
+                            dist[v] = dist[u] + w;
+                            stack.push(v);
+                        }
+                    }
+                }
+                let expected = (
+                    *dist.iter().max().unwrap(),
+                    n as i64,
+                    dist.iter().sum::<i64>() + (0..n as i64).sum::<i64>(),
+                );
+                assert_eq!(got, expected);
+            }
+        }
+
+        // A long path must not overflow the stack
+        let n = 200_000;
+        let mut tree = Tree::new(n);
+        for v in 1..n {
+            tree.add_edge(v - 1, v, 1);
+        }
+        let res = tree.rerooting((0, 0, 0), merge, add_edge, add_vertex);
+        assert_eq!(res[0].0, n as i64 - 1);
+        assert_eq!(res[n / 2].0, (n / 2) as i64);
+    }
 }
@@ -0,0 +1,229 @@
+#![allow(dead_code)]
+
+// INJECT: src/template/graph/scc.rs
+
+// --- SNAP START ---
+
+/// 2-SAT Solver
+///
+/// Decides whether a conjunction of clauses `(x_i == f) || (x_j == g)` over boolean variables is satisfiable,
+/// and if so finds an assignment. The implication graph is solved with `SccGraph::scc`.
+///
+/// A literal is a pair `(i, f)` meaning `x_i == f`.
+/// `at_most_one` adds auxiliary variables internally; they are not part of the returned assignment.
+///
+/// # Examples
+///
+/// ```
+/// let mut ts = TwoSat::new(3);
+/// ts.add_clause(0, true, 1, true); // x0 || x1
+/// ts.implies(1, true, 2, false); // x1 -> !x2
+/// ts.xor(0, 1); // x0 != x1
+/// ts.set(2, true); // x2
+/// assert_eq!(ts.satisfiable(), Some(vec![true, false, true]));
+///
+/// ts.at_most_one(&[(0, true), (2, true)]); // x0 and x2 cannot both hold
+/// assert_eq!(ts.satisfiable(), None);
+/// ```
+///
+/// # Complexity
+/// - O(N + M) for `satisfiable`, where M is the number of clauses
+/// - `at_most_one` adds O(K) variables and clauses for K literals
+pub struct TwoSat {
+    n: usize,
+    // Including auxiliary variables
+    vars: usize,
+    // Implication edges between literal nodes
+    edges: Vec<(usize, usize)>,
+}
+
+impl TwoSat {
+    /// Creates a solver with `n` variables.
+    pub fn new(n: usize) -> Self {
+        TwoSat {
+            n,
+            vars: n,
+            edges: Vec::new(),
+        }
+    }
+
+    // Node of the literal `x_i == f`
+    fn node(
+        i: usize,
+        f: bool,
+    ) -> usize {
+        2 * i + f as usize
+    }
+
+    /// Adds the clause `(x_i == f) || (x_j == g)`.
+    pub fn add_clause(
+        &mut self,
+        i: usize,
+        f: bool,
+        j: usize,
+        g: bool,
+    ) {
+        assert!(i < self.n && j < self.n, "Variable out of range");
+        self.push_clause(i, f, j, g);
+    }
+
+    // `add_clause` without the range check, so that auxiliary variables can be used
+    fn push_clause(
+        &mut self,
+        i: usize,
+        f: bool,
+        j: usize,
+        g: bool,
+    ) {
+        self.edges.push((Self::node(i, !f), Self::node(j, g)));
+        self.edges.push((Self::node(j, !g), Self::node(i, f)));
+    }
+
+    /// Forces `x_i == f`.
+    pub fn set(
+        &mut self,
+        i: usize,
+        f: bool,
+    ) {
+        self.add_clause(i, f, i, f);
+    }
+
+    /// Adds `(x_i == f) -> (x_j == g)`.
+    pub fn implies(
+        &mut self,
+        i: usize,
+        f: bool,
+        j: usize,
+        g: bool,
+    ) {
+        self.add_clause(i, !f, j, g);
+    }
+
+    /// Adds `x_i != x_j`.
+    pub fn xor(
+        &mut self,
+        i: usize,
+        j: usize,
+    ) {
+        self.add_clause(i, true, j, true);
+        self.add_clause(i, false, j, false);
+    }
+
+    /// Adds the constraint that at most one of `literals` holds.
+    ///
+    /// Uses auxiliary variables `s_k = (some of literals[..=k] holds)`,
+    /// so the number of clauses is linear instead of quadratic.
+    pub fn at_most_one(
+        &mut self,
+        literals: &[(usize, bool)],
+    ) {
+        assert!(literals.iter().all(|&(i, _)| i < self.n), "Variable out of range");
+        let base = self.vars;
+        self.vars += literals.len();
+        for (k, &(i, f)) in literals.iter().enumerate() {
+            // (x_i == f) -> s_k
+            self.push_clause(i, !f, base + k, true);
+            if k > 0 {
+                // s_{k-1} -> s_k, s_{k-1} -> (x_i != f)
+                self.push_clause(base + k - 1, false, base + k, true);
+                self.push_clause(base + k - 1, false, i, !f);
+            }
+        }
+    }
+
+    /// Returns an assignment of the `n` variables satisfying every clause, or `None` if there is none.
+    pub fn satisfiable(&self) -> Option<Vec<bool>> {
+        let mut graph = SccGraph::new(2 * self.vars);
+        graph.edges = self.edges.clone();
+        let ids = graph.scc().ids;
+        if (0..self.vars).any(|i| ids[Self::node(i, true)] == ids[Self::node(i, false)]) {
+            return None;
+        }
+
+        // The literal later in topological order is the one that can be true
+        Some(
+            (0..self.n)
+                .map(|i| ids[Self::node(i, true)] > ids[Self::node(i, false)])
+                .collect(),
+        )
+    }
+}
+
+// --- SNAP END ---
+
+#[allow(unused_imports)]
+use super::scc::SccGraph;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_two_sat_random() {
+        let mut seed: u64 = 0x243F_6A88_85A3_08D3;
+        let mut next = |m: u64| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed % m
+        };
+        for _ in 0..500 {
+            let n = next(8) as usize + 1;
+            let mut ts = TwoSat::new(n);
+            let mut clauses = Vec::new();
+            let mut groups = Vec::new();
+            for _ in 0..next(12) {
+                let lit = |r: u64| ((r / 2) as usize % n, r % 2 == 1);
+                let (i, f) = lit(next(1 << 20));
+                let (j, g) = lit(next(1 << 20));
+                match next(4) {
+                    0 => {
+                        ts.implies(i, f, j, g);
+                        clauses.push((i, !f, j, g));
+                    }
+                    1 => {
+                        ts.xor(i, j);
+                        clauses.push((i, true, j, true));
+                        clauses.push((i, false, j, false));
+                    }
+                    2 => {
+                        let group: Vec<(usize, bool)> = (0..next(5)).map(|_| lit(next(1 << 20))).collect();
+                        ts.at_most_one(&group);
+                        groups.push(group);
+                    }
+                    _ => {
+                        ts.add_clause(i, f, j, g);
+                        clauses.push((i, f, j, g));
+                    }
+                }
+            }
+
+            let ok = |x: &[bool]| {
+                clauses.iter().all(|&(i, f, j, g)| x[i] == f || x[j] == g)
+                    && groups
+                        .iter()
+                        .all(|gr| gr.iter().filter(|&&(i, f)| x[i] == f).count() <= 1)
+            };
+            let exists = (0..1u32 << n).any(|mask| {
+                let x: Vec<bool> = (0..n).map(|i| mask >> i & 1 == 1).collect();
+                ok(&x)
+            });
+            match ts.satisfiable() {
+                Some(x) => {
+                    assert_eq!(x.len(), n);
+                    assert!(ok(&x));
+                }
+                None => assert!(!exists),
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Variable out of range")]
+    fn test_two_sat_auxiliary_out_of_range() {
+        let mut ts = TwoSat::new(2);
+        ts.at_most_one(&[(0, true), (1, true)]);
+        // Variable 2 is auxiliary, not one of the user's
+        ts.set(2, true);
+    }
+}
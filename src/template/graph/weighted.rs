@@ -1,5 +1,7 @@
 #![allow(dead_code)]
 
+// INJECT: src/template/data_structures/mergeable_heap.rs
+
 use std::cmp::Reverse;
 use std::collections::BinaryHeap;
 
@@ -20,6 +22,8 @@ pub struct Edge {
 ///
 /// # Supported Algorithms
 /// - **Dijkstra**: Single-Source Shortest Path (Non-negative weights). $O(E \log V)$
+/// - **Dijkstra (Radix Heap)**: Same as above, faster on large inputs. $O(E + V \log C)$
+/// - **Dijkstra (Pairing Heap)**: Same as above, with one heap entry per vertex via `decrease_key`. $O(E + V \log V)$ amortized
 /// - **Bellman-Ford**: Single-Source Shortest Path (Negative weights allowed). Detects negative cycles. $O(V \times E)$
 /// - **Warshall-Floyd**: All-Pairs Shortest Path. $O(V^3)$
 /// - **Kruskal**: Minimum Spanning Tree (MST). $O(E \log E)$
@@ -141,6 +145,70 @@ impl WeightedGraph {
         dist
     }
 
+    /// Runs Dijkstra's algorithm with a radix heap. O(E + V log C) where C is the maximum distance.
+    /// Same result as `dijkstra`, but usually faster on large graphs (e.g. 2e5 vertices, 1e6 edges).
+    ///
+    /// # Panics
+    /// Panics if a negative edge weight is found.
+    pub fn dijkstra_radix(
+        &self,
+        start: usize,
+    ) -> Vec<i64> {
+        let mut dist = vec![INF_I64; self.n];
+        let mut pq = RadixHeap::new();
+
+        dist[start] = 0;
+        pq.push(0, start);
+
+        while let Some((d, u)) = pq.pop() {
+            let d = d as i64;
+            if d > dist[u] {
+                continue;
+            }
+
+            for &(v, w) in &self.adj[u] {
+                assert!(w >= 0, "dijkstra_radix requires non-negative weights");
+                if d + w < dist[v] {
+                    dist[v] = d + w;
+                    pq.push(dist[v] as u64, v);
+                }
+            }
+        }
+        dist
+    }
+
+    /// Runs Dijkstra's algorithm with a pairing heap (see `mergeable_heap.rs`).
+    /// Keeps one heap entry per vertex and lowers it with `decrease_key` instead of pushing duplicates,
+    /// which helps on dense graphs where E is much larger than V.
+    ///
+    /// # Panics
+    /// Panics if a negative edge weight is found.
+    pub fn dijkstra_pairing(
+        &self,
+        start: usize,
+    ) -> Vec<i64> {
+        let mut dist = vec![INF_I64; self.n];
+        let mut handle = vec![None; self.n];
+        let mut pq = PairingHeap::new();
+
+        dist[start] = 0;
+        handle[start] = Some(pq.push(0, start));
+
+        while let Some((d, u)) = pq.pop() {
+            for &(v, w) in &self.adj[u] {
+                assert!(w >= 0, "dijkstra_pairing requires non-negative weights");
+                if d + w < dist[v] {
+                    dist[v] = d + w;
+                    match handle[v] {
+                        Some(h) => pq.decrease_key(h, dist[v]),
+                        None => handle[v] = Some(pq.push(dist[v], v)),
+                    }
+                }
+            }
+        }
+        dist
+    }
+
     // ====================================================
     // 2. Bellman-Ford
     // ====================================================
@@ -301,8 +369,102 @@ impl UnionFind {
     }
 }
 
+/// Radix Heap (Monotone Min-Priority Queue for u64 Keys)
+///
+/// A key pushed must not be smaller than the last popped key, which always holds in Dijkstra's algorithm.
+/// Elements are bucketed by the highest bit that differs from the last popped key,
+/// so each element is moved at most 64 times.
+///
+/// # Examples
+///
+/// ```
+/// let mut pq = RadixHeap::new();
+/// pq.push(5, 'a');
+/// pq.push(2, 'b');
+/// assert_eq!(pq.pop(), Some((2, 'b')));
+/// pq.push(3, 'c');
+/// assert_eq!(pq.pop(), Some((3, 'c')));
+/// assert_eq!(pq.pop(), Some((5, 'a')));
+/// ```
+///
+/// # Complexity
+/// - `push`: O(1)
+/// - `pop`: amortized O(log C)
+pub struct RadixHeap<V> {
+    buckets: Vec<Vec<(u64, V)>>,
+    last: u64,
+    len: usize,
+}
+
+impl<V> Default for RadixHeap<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V> RadixHeap<V> {
+    pub fn new() -> Self {
+        RadixHeap {
+            buckets: (0..65).map(|_| Vec::new()).collect(),
+            last: 0,
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn bucket(
+        &self,
+        key: u64,
+    ) -> usize {
+        64 - (key ^ self.last).leading_zeros() as usize
+    }
+
+    /// Pushes `value` with priority `key`.
+    ///
+    /// # Panics
+    /// Panics if `key` is smaller than the last popped key.
+    pub fn push(
+        &mut self,
+        key: u64,
+        value: V,
+    ) {
+        assert!(key >= self.last, "Keys must be monotone");
+        let b = self.bucket(key);
+        self.buckets[b].push((key, value));
+        self.len += 1;
+    }
+
+    /// Removes and returns an element with the minimum key.
+    pub fn pop(&mut self) -> Option<(u64, V)> {
+        if self.len == 0 {
+            return None;
+        }
+        if self.buckets[0].is_empty() {
+            let i = (1..65).find(|&i| !self.buckets[i].is_empty()).unwrap();
+            let items = std::mem::take(&mut self.buckets[i]);
+            self.last = items.iter().map(|&(k, _)| k).min().unwrap();
+            for (k, v) in items {
+                let b = self.bucket(k);
+                self.buckets[b].push((k, v));
+            }
+        }
+        self.len -= 1;
+        self.buckets[0].pop()
+    }
+}
+
 // --- SNAP END ---
 
+#[allow(unused_imports)]
+use super::super::data_structures::mergeable_heap::PairingHeap;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -314,4 +476,58 @@ mod tests {
         assert!(uf.same(0, 1));
         assert!(!uf.same(0, 2));
     }
+
+    #[test]
+    fn test_dijkstra_radix() {
+        let mut seed: u64 = 0x3C6E_F372_FE94_F82B;
+        let mut next = |m: u64| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed % m
+        };
+        let n = 300;
+        let mut graph = WeightedGraph::new(n);
+        for _ in 0..2000 {
+            let u = next(n as u64) as usize;
+            let v = next(n as u64) as usize;
+            // Include zero-weight and huge edges
+            let w = match next(3) {
+                0 => 0,
+                1 => next(10) as i64,
+                _ => next(1 << 40) as i64,
+            };
+            graph.add_edge(u, v, w);
+        }
+        for start in [0, 1, n - 1] {
+            assert_eq!(graph.dijkstra_radix(start), graph.dijkstra(start));
+        }
+    }
+
+    #[test]
+    fn test_dijkstra_pairing() {
+        let mut seed: u64 = 0xA54F_F53A_5F1D_36F1;
+        let mut next = |m: u64| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed % m
+        };
+        // Dense graph, so that decrease_key is hit often
+        let n = 100;
+        let mut graph = WeightedGraph::new(n);
+        for _ in 0..3000 {
+            let u = next(n as u64) as usize;
+            let v = next(n as u64) as usize;
+            let w = match next(3) {
+                0 => 0,
+                1 => next(10) as i64,
+                _ => next(1 << 40) as i64,
+            };
+            graph.add_edge(u, v, w);
+        }
+        for start in [0, 1, n - 1] {
+            assert_eq!(graph.dijkstra_pairing(start), graph.dijkstra(start));
+        }
+    }
 }
@@ -0,0 +1,246 @@
+#![allow(dead_code)]
+use std::collections::BTreeMap;
+
+// --- SNAP START ---
+
+/// Interval Map (Chtholly Tree / ODT)
+///
+/// Manages values on integer points as runs `[l, r) -> v`, the value-carrying counterpart of `RangeSet`.
+/// Points that were never assigned (or were erased) have no value.
+/// Adjacent runs with equal values are merged on `assign`.
+///
+/// `assign_with` / `erase_with` call back for every piece of an existing run that gets overwritten,
+/// so aggregates like "number of cells with color c" can be kept in sync.
+///
+/// # Examples
+///
+/// ```
+/// let mut im = IntervalMap::new();
+/// im.assign(0, 10, 'a');
+/// im.assign(3, 5, 'b');
+/// assert_eq!(im.get(4), Some(&'b'));
+/// assert_eq!(im.get(10), None);
+///
+/// let runs: Vec<_> = im.runs(2, 7).collect();
+/// assert_eq!(runs, vec![(2, 3, &'a'), (3, 5, &'b'), (5, 7, &'a')]);
+///
+/// // Count of cells per color, maintained while overwriting
+/// let mut cnt = std::collections::HashMap::new();
+/// cnt.insert('a', 8);
+/// cnt.insert('b', 2);
+/// im.assign_with(4, 8, 'c', |l, r, v| *cnt.get_mut(v).unwrap() -= r - l);
+/// *cnt.entry('c').or_insert(0) += 4;
+/// assert_eq!(cnt[&'a'], 5);
+/// assert_eq!(cnt[&'b'], 1);
+/// ```
+///
+/// # Complexity
+/// - `assign`, `erase`: amortized O(log N) (each call adds O(1) runs and removes every run it covers)
+/// - `get`, `split_at`: O(log N)
+#[derive(Debug, Clone)]
+pub struct IntervalMap<V> {
+    // l -> (r, v) for the run [l, r)
+    map: BTreeMap<i64, (i64, V)>,
+}
+
+impl<V: Clone + PartialEq> Default for IntervalMap<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V: Clone + PartialEq> IntervalMap<V> {
+    pub fn new() -> Self {
+        Self {
+            map: BTreeMap::new(),
+        }
+    }
+
+    /// Splits the run containing `x` (if any) into `[l, x)` and `[x, r)`.
+    pub fn split_at(
+        &mut self,
+        x: i64,
+    ) {
+        if let Some((&l, (r, v))) = self.map.range(..x).next_back() {
+            let (r, v) = (*r, v.clone());
+            if r > x {
+                self.map.insert(l, (x, v.clone()));
+                self.map.insert(x, (r, v));
+            }
+        }
+    }
+
+    /// Removes all values on `[l, r)`, calling `f(l', r', &v)` for every removed piece.
+    pub fn erase_with<F: FnMut(i64, i64, &V)>(
+        &mut self,
+        l: i64,
+        r: i64,
+        mut f: F,
+    ) {
+        if l >= r {
+            return;
+        }
+        self.split_at(l);
+        self.split_at(r);
+        let keys: Vec<i64> = self.map.range(l..r).map(|(&k, _)| k).collect();
+        for k in keys {
+            let (kr, v) = self.map.remove(&k).unwrap();
+            f(k, kr, &v);
+        }
+    }
+
+    /// Removes all values on `[l, r)`.
+    pub fn erase(
+        &mut self,
+        l: i64,
+        r: i64,
+    ) {
+        self.erase_with(l, r, |_, _, _| {});
+    }
+
+    /// Sets the value of every point in `[l, r)` to `v`,
+    /// calling `f(l', r', &old)` for every overwritten piece before it disappears.
+    pub fn assign_with<F: FnMut(i64, i64, &V)>(
+        &mut self,
+        mut l: i64,
+        mut r: i64,
+        v: V,
+        f: F,
+    ) {
+        if l >= r {
+            return;
+        }
+        self.erase_with(l, r, f);
+
+        // Merge with neighbors holding the same value
+        if let Some((&pl, (pr, pv))) = self.map.range(..l).next_back()
+            && *pr == l
+            && *pv == v
+        {
+            l = pl;
+            self.map.remove(&pl);
+        }
+        if let Some(&(nr, ref nv)) = self.map.get(&r)
+            && *nv == v
+        {
+            self.map.remove(&r);
+            r = nr;
+        }
+        self.map.insert(l, (r, v));
+    }
+
+    /// Sets the value of every point in `[l, r)` to `v`.
+    pub fn assign(
+        &mut self,
+        l: i64,
+        r: i64,
+        v: V,
+    ) {
+        self.assign_with(l, r, v, |_, _, _| {});
+    }
+
+    /// Returns the run `(l, r, &v)` containing `x`.
+    pub fn get_run(
+        &self,
+        x: i64,
+    ) -> Option<(i64, i64, &V)> {
+        let (&l, (r, v)) = self.map.range(..=x).next_back()?;
+        if *r > x {
+            Some((l, *r, v))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the value at `x`.
+    pub fn get(
+        &self,
+        x: i64,
+    ) -> Option<&V> {
+        self.get_run(x).map(|(_, _, v)| v)
+    }
+
+    /// Iterates over the runs intersecting `[l, r)` in increasing order, clipped to `[l, r)`.
+    pub fn runs(
+        &self,
+        l: i64,
+        r: i64,
+    ) -> impl Iterator<Item = (i64, i64, &V)> + '_ {
+        let (first, rest) = if l < r {
+            let first = self.map.range(..l).next_back().filter(|(_, (rr, _))| *rr > l);
+            (first, Some(self.map.range(l..r)))
+        } else {
+            (None, None)
+        };
+        first
+            .into_iter()
+            .chain(rest.into_iter().flatten())
+            .map(move |(&a, (b, v))| (a.max(l), (*b).min(r), v))
+    }
+
+    /// Returns the number of runs.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+}
+
+// --- SNAP END ---
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interval_map_random() {
+        let mut seed: u64 = 0xA5A5_5A5A_1234_4321;
+        let mut next = |m: u64| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed % m
+        };
+        const N: i64 = 40;
+        let mut im = IntervalMap::new();
+        let mut naive: Vec<Option<u64>> = vec![None; N as usize];
+        let mut count = [0i64; 4];
+        for _ in 0..2000 {
+            let l = next(N as u64 + 1) as i64;
+            let r = next(N as u64 + 1) as i64;
+            let (l, r) = (l.min(r), l.max(r));
+            match next(4) {
+                0 => im.split_at(l),
+                1 => {
+                    im.erase_with(l, r, |a, b, &v| count[v as usize] -= b - a);
+                    naive[l as usize..r as usize].iter_mut().for_each(|x| *x = None);
+                }
+                _ => {
+                    let v = next(4);
+                    im.assign_with(l, r, v, |a, b, &v| count[v as usize] -= b - a);
+                    count[v as usize] += r - l;
+                    naive[l as usize..r as usize].iter_mut().for_each(|x| *x = Some(v));
+                }
+            }
+            for x in 0..N {
+                assert_eq!(im.get(x).copied(), naive[x as usize]);
+            }
+            for (v, &c) in count.iter().enumerate() {
+                assert_eq!(c, naive.iter().filter(|&&x| x == Some(v as u64)).count() as i64);
+            }
+            let mut covered = vec![None; N as usize];
+            for (a, b, &v) in im.runs(l, r) {
+                assert!(l <= a && a < b && b <= r);
+                for x in a..b {
+                    covered[x as usize] = Some(v);
+                }
+            }
+            for x in 0..N {
+                let expected = if l <= x && x < r { naive[x as usize] } else { None };
+                assert_eq!(covered[x as usize], expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,397 @@
+#![allow(dead_code)]
+
+// --- SNAP START ---
+
+/// Mo's Algorithm (Offline Range Queries, Hilbert Order)
+///
+/// Answers offline queries on `[l, r)` by moving a window one element at a time.
+/// Queries are sorted along a Hilbert curve, which keeps the total movement at O(N sqrt Q).
+///
+/// The window state `S` is passed to every callback:
+/// - `add_left(s, i)` / `add_right(s, i)`: element `i` enters the window from the left / right
+/// - `remove_left(s, i)` / `remove_right(s, i)`: element `i` leaves the window from the left / right
+/// - `answer(s, i)`: the answer for the current window, which is the range of query `i`
+///
+/// For Mo on trees, see `Tree::mo_path_ranges`.
+///
+/// # Examples
+///
+/// ```
+/// // Number of distinct values in a[l..r]
+/// let a = vec![1, 2, 1, 3, 2];
+/// let mut mo = Mo::new(a.len());
+/// mo.add_query(0, 3);
+/// mo.add_query(1, 5);
+/// mo.add_query(2, 3);
+///
+/// let mut state = (vec![0; 4], 0); // (count per value, distinct)
+/// let add = |s: &mut (Vec<usize>, usize), i: usize| {
+///     s.0[a[i]] += 1;
+///     if s.0[a[i]] == 1 {
+///         s.1 += 1;
+///     }
+/// };
+/// let remove = |s: &mut (Vec<usize>, usize), i: usize| {
+///     s.0[a[i]] -= 1;
+///     if s.0[a[i]] == 0 {
+///         s.1 -= 1;
+///     }
+/// };
+/// let res = mo.solve(&mut state, add, add, remove, remove, |s, _| s.1);
+/// assert_eq!(res, vec![2, 3, 1]);
+/// ```
+///
+/// # Complexity
+/// - O(N sqrt Q) callback calls + O(Q log Q) for sorting
+#[derive(Debug, Clone)]
+pub struct Mo {
+    n: usize,
+    queries: Vec<(usize, usize)>,
+}
+
+/// Returns the position of `(x, y)` along the Hilbert curve filling a `2^log x 2^log` grid.
+fn hilbert_order(
+    mut x: u64,
+    mut y: u64,
+    log: u32,
+) -> u64 {
+    let n = 1u64 << log;
+    let mut d = 0;
+    let mut s = n >> 1;
+    while s > 0 {
+        let rx = (x & s > 0) as u64;
+        let ry = (y & s > 0) as u64;
+        d += s * s * ((3 * rx) ^ ry);
+        if ry == 0 {
+            if rx == 1 {
+                x = n - 1 - x;
+                y = n - 1 - y;
+            }
+            std::mem::swap(&mut x, &mut y);
+        }
+        s >>= 1;
+    }
+    d
+}
+
+impl Mo {
+    /// Creates a runner for queries on `[0, n)`.
+    pub fn new(n: usize) -> Self {
+        Mo {
+            n,
+            queries: Vec::new(),
+        }
+    }
+
+    /// Adds the query `[l, r)` and returns its index.
+    pub fn add_query(
+        &mut self,
+        l: usize,
+        r: usize,
+    ) -> usize {
+        assert!(l <= r && r <= self.n, "Invalid range");
+        self.queries.push((l, r));
+        self.queries.len() - 1
+    }
+
+    /// Runs all queries and returns the answers in the order they were added.
+    pub fn solve<S, R>(
+        &self,
+        state: &mut S,
+        mut add_left: impl FnMut(&mut S, usize),
+        mut add_right: impl FnMut(&mut S, usize),
+        mut remove_left: impl FnMut(&mut S, usize),
+        mut remove_right: impl FnMut(&mut S, usize),
+        mut answer: impl FnMut(&mut S, usize) -> R,
+    ) -> Vec<R> {
+        let q = self.queries.len();
+        let log = (self.n + 1).next_power_of_two().trailing_zeros().max(1);
+        let keys: Vec<u64> = self
+            .queries
+            .iter()
+            .map(|&(l, r)| hilbert_order(l as u64, r as u64, log))
+            .collect();
+        let mut order: Vec<usize> = (0..q).collect();
+        order.sort_by_key(|&i| keys[i]);
+
+        let mut res: Vec<Option<R>> = (0..q).map(|_| None).collect();
+        let (mut cl, mut cr) = (0, 0);
+        for i in order {
+            let (l, r) = self.queries[i];
+            // Extend first so that the window never becomes invalid
+            while cl > l {
+                cl -= 1;
+                add_left(state, cl);
+            }
+            while cr < r {
+                add_right(state, cr);
+                cr += 1;
+            }
+            while cl < l {
+                remove_left(state, cl);
+                cl += 1;
+            }
+            while cr > r {
+                cr -= 1;
+                remove_right(state, cr);
+            }
+            res[i] = Some(answer(state, i));
+        }
+        res.into_iter().map(|x| x.unwrap()).collect()
+    }
+}
+
+/// Mo's Algorithm with Updates (Offline Range Queries with Point Updates)
+///
+/// Adds a time dimension to `Mo`: each query sees exactly the updates registered before it.
+/// Queries are sorted by `(l / B, r / B, t)` with `B = N^(2/3)`.
+///
+/// Callbacks:
+/// - `add(s, i)` / `remove(s, i)`: element `i` enters / leaves the window
+/// - `toggle(s, t, l, r)`: applies update `t` if it is not applied, otherwise undoes it,
+///   while the window is `[l, r)`. The usual way is to swap the array value with the value stored in the update,
+///   calling `remove` / `add` around it if the position is inside the window.
+/// - `answer(s, i)`: the answer for the current window, which is the range of query `i`
+///
+/// # Examples
+///
+/// ```
+/// // Range sum with point assignment
+/// let mut mo = MoWithUpdates::new(3);
+/// mo.add_query(0, 3); // [1, 2, 3]
+/// mo.add_update(); // a[1] = 10
+/// mo.add_query(0, 2); // [1, 10]
+///
+/// let mut state = (vec![1i64, 2, 3], vec![(1, 10i64)], 0i64); // (array, updates, sum)
+/// let res = mo.solve(
+///     &mut state,
+///     |s, i| s.2 += s.0[i],
+///     |s, i| s.2 -= s.0[i],
+///     |s, t, l, r| {
+///         let (p, x) = s.1[t];
+///         if l <= p && p < r {
+///             s.2 += x - s.0[p];
+///         }
+///         s.1[t].1 = s.0[p];
+///         s.0[p] = x;
+///     },
+///     |s, _| s.2,
+/// );
+/// assert_eq!(res, vec![6, 11]);
+/// ```
+///
+/// # Complexity
+/// - O(N^(5/3)) callback calls when N, Q and the number of updates are of the same order
+#[derive(Debug, Clone)]
+pub struct MoWithUpdates {
+    n: usize,
+    // (l, r, number of updates before the query)
+    queries: Vec<(usize, usize, usize)>,
+    updates: usize,
+}
+
+impl MoWithUpdates {
+    pub fn new(n: usize) -> Self {
+        MoWithUpdates {
+            n,
+            queries: Vec::new(),
+            updates: 0,
+        }
+    }
+
+    /// Registers the next update and returns its index (the data of the update is kept by the caller).
+    pub fn add_update(&mut self) -> usize {
+        self.updates += 1;
+        self.updates - 1
+    }
+
+    /// Adds the query `[l, r)` after all updates registered so far, and returns its index.
+    pub fn add_query(
+        &mut self,
+        l: usize,
+        r: usize,
+    ) -> usize {
+        assert!(l <= r && r <= self.n, "Invalid range");
+        self.queries.push((l, r, self.updates));
+        self.queries.len() - 1
+    }
+
+    /// Runs all queries and returns the answers in the order they were added.
+    pub fn solve<S, R>(
+        &self,
+        state: &mut S,
+        mut add: impl FnMut(&mut S, usize),
+        mut remove: impl FnMut(&mut S, usize),
+        mut toggle: impl FnMut(&mut S, usize, usize, usize),
+        mut answer: impl FnMut(&mut S, usize) -> R,
+    ) -> Vec<R> {
+        let q = self.queries.len();
+        let block = ((self.n.max(1) as f64).powf(2.0 / 3.0) as usize).max(1);
+        let mut order: Vec<usize> = (0..q).collect();
+        order.sort_by_key(|&i| {
+            let (l, r, t) = self.queries[i];
+            let (bl, br) = (l / block, r / block);
+            // Snake order on r and t to halve the movement
+            let br = if bl % 2 == 0 { br } else { usize::MAX - br };
+            let t = if br % 2 == 0 { t } else { usize::MAX - t };
+            (bl, br, t)
+        });
+
+        let mut res: Vec<Option<R>> = (0..q).map(|_| None).collect();
+        let (mut cl, mut cr, mut ct) = (0, 0, 0);
+        for i in order {
+            let (l, r, t) = self.queries[i];
+            while cl > l {
+                cl -= 1;
+                add(state, cl);
+            }
+            while cr < r {
+                add(state, cr);
+                cr += 1;
+            }
+            while cl < l {
+                remove(state, cl);
+                cl += 1;
+            }
+            while cr > r {
+                cr -= 1;
+                remove(state, cr);
+            }
+            while ct < t {
+                toggle(state, ct, cl, cr);
+                ct += 1;
+            }
+            while ct > t {
+                ct -= 1;
+                toggle(state, ct, cl, cr);
+            }
+            res[i] = Some(answer(state, i));
+        }
+        res.into_iter().map(|x| x.unwrap()).collect()
+    }
+}
+
+// --- SNAP END ---
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mo_distinct_count() {
+        let mut seed: u64 = 0xBB67_AE85_84CA_A73B;
+        let mut next = |m: u64| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed % m
+        };
+        let n = 100;
+        let a: Vec<usize> = (0..n).map(|_| next(10) as usize).collect();
+        let mut mo = Mo::new(n);
+        let mut expected = Vec::new();
+        for _ in 0..300 {
+            let l = next(n as u64 + 1) as usize;
+            let r = next(n as u64 + 1) as usize;
+            let (l, r) = (l.min(r), l.max(r));
+            mo.add_query(l, r);
+            let mut seen = a[l..r].to_vec();
+            seen.sort();
+            seen.dedup();
+            expected.push(seen.len());
+        }
+        let mut state = (vec![0; 10], 0);
+        let add = |s: &mut (Vec<usize>, usize), i: usize| {
+            s.0[a[i]] += 1;
+            if s.0[a[i]] == 1 {
+                s.1 += 1;
+            }
+        };
+        let remove = |s: &mut (Vec<usize>, usize), i: usize| {
+            s.0[a[i]] -= 1;
+            if s.0[a[i]] == 0 {
+                s.1 -= 1;
+            }
+        };
+        assert_eq!(mo.solve(&mut state, add, add, remove, remove, |s, _| s.1), expected);
+    }
+
+    #[test]
+    fn test_mo_with_updates() {
+        let mut seed: u64 = 0x3C6E_F372_FE94_F82B;
+        let mut next = |m: u64| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed % m
+        };
+        let n = 50;
+        let mut a: Vec<usize> = (0..n).map(|_| next(8) as usize).collect();
+        let init = a.clone();
+        let mut mo = MoWithUpdates::new(n);
+        let mut updates = Vec::new();
+        let mut expected = Vec::new();
+        for _ in 0..300 {
+            if next(2) == 0 {
+                let p = next(n as u64) as usize;
+                let x = next(8) as usize;
+                mo.add_update();
+                updates.push((p, x));
+                a[p] = x;
+            } else {
+                let l = next(n as u64 + 1) as usize;
+                let r = next(n as u64 + 1) as usize;
+                let (l, r) = (l.min(r), l.max(r));
+                mo.add_query(l, r);
+                let mut seen = a[l..r].to_vec();
+                seen.sort();
+                seen.dedup();
+                expected.push(seen.len());
+            }
+        }
+
+        struct State {
+            a: Vec<usize>,
+            updates: Vec<(usize, usize)>,
+            cnt: Vec<usize>,
+            distinct: usize,
+        }
+        fn add(
+            s: &mut State,
+            i: usize,
+        ) {
+            s.cnt[s.a[i]] += 1;
+            if s.cnt[s.a[i]] == 1 {
+                s.distinct += 1;
+            }
+        }
+        fn remove(
+            s: &mut State,
+            i: usize,
+        ) {
+            s.cnt[s.a[i]] -= 1;
+            if s.cnt[s.a[i]] == 0 {
+                s.distinct -= 1;
+            }
+        }
+        let mut state = State {
+            a: init,
+            updates,
+            cnt: vec![0; 8],
+            distinct: 0,
+        };
+        let toggle = |s: &mut State, t: usize, l: usize, r: usize| {
+            let (p, x) = s.updates[t];
+            let inside = l <= p && p < r;
+            if inside {
+                remove(s, p);
+            }
+            s.updates[t].1 = s.a[p];
+            s.a[p] = x;
+            if inside {
+                add(s, p);
+            }
+        };
+        assert_eq!(mo.solve(&mut state, add, remove, toggle, |s, _| s.distinct), expected);
+    }
+}
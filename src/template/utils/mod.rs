@@ -1,6 +1,9 @@
 mod coordinate_compression;
 mod ext_gcd;
 mod get_next_position;
+mod interval_map;
 mod interval_set;
 mod longest_increasing_subsequence;
+mod mo;
 mod mod_pow;
+mod sliding_window;
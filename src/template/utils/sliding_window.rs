@@ -0,0 +1,337 @@
+#![allow(dead_code)]
+use std::collections::VecDeque;
+
+// --- SNAP START ---
+
+/// Foldable Queue (Sliding Window Aggregation, Two Stacks)
+///
+/// A FIFO queue that can return the product of all its elements (front to back) at any time.
+/// Only associativity is required, so it works for non-invertible operations
+/// such as min, gcd, matrix products and affine compositions.
+/// Takes the same `(op, identity)` description as `SegmentTree::new`.
+///
+/// # Examples
+///
+/// ```
+/// // Composition of affine maps (a, b): x -> a * x + b, applied front to back
+/// let mut q = FoldableQueue::new(|f: (i64, i64), g: (i64, i64)| (f.0 * g.0, f.1 * g.0 + g.1), (1, 0));
+/// q.push_back((2, 1));
+/// q.push_back((3, 0));
+/// assert_eq!(q.fold(), (6, 3));
+/// q.pop_front();
+/// q.push_back((1, 5));
+/// assert_eq!(q.fold(), (3, 5));
+/// ```
+///
+/// # Complexity
+/// - `push_back`, `fold`: O(1)
+/// - `pop_front`: amortized O(1)
+#[derive(Debug, Clone)]
+pub struct FoldableQueue<T, F> {
+    // front: (value, product of this value and everything above it in the stack), top is the frontmost
+    front: Vec<(T, T)>,
+    // back: (value, product of everything below and this value), top is the backmost
+    back: Vec<(T, T)>,
+    op: F,
+    identity: T,
+}
+
+impl<T, F> FoldableQueue<T, F>
+where
+    T: Clone,
+    F: Fn(T, T) -> T,
+{
+    pub fn new(
+        op: F,
+        identity: T,
+    ) -> Self {
+        FoldableQueue {
+            front: Vec::new(),
+            back: Vec::new(),
+            op,
+            identity,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn push_back(
+        &mut self,
+        x: T,
+    ) {
+        let acc = match self.back.last() {
+            Some((_, acc)) => (self.op)(acc.clone(), x.clone()),
+            None => x.clone(),
+        };
+        self.back.push((x, acc));
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.front.is_empty() {
+            while let Some((x, _)) = self.back.pop() {
+                let acc = match self.front.last() {
+                    Some((_, acc)) => (self.op)(x.clone(), acc.clone()),
+                    None => x.clone(),
+                };
+                self.front.push((x, acc));
+            }
+        }
+        self.front.pop().map(|(x, _)| x)
+    }
+
+    /// Returns the product of all elements from front to back (the identity if empty).
+    pub fn fold(&self) -> T {
+        let f = self.front.last().map_or(self.identity.clone(), |(_, acc)| acc.clone());
+        let b = self.back.last().map_or(self.identity.clone(), |(_, acc)| acc.clone());
+        (self.op)(f, b)
+    }
+}
+
+/// Foldable Deque (Sliding Window Aggregation on both ends)
+///
+/// Same as `FoldableQueue`, but elements can be pushed and popped at both ends.
+/// When one side runs out, the other side is split in half, which keeps every operation amortized O(1).
+///
+/// # Examples
+///
+/// ```
+/// let mut dq = FoldableDeque::new(|a: String, b: String| a + &b, String::new());
+/// dq.push_back("b".to_string());
+/// dq.push_front("a".to_string());
+/// dq.push_back("c".to_string());
+/// assert_eq!(dq.fold(), "abc");
+/// assert_eq!(dq.pop_back(), Some("c".to_string()));
+/// assert_eq!(dq.pop_back(), Some("b".to_string()));
+/// assert_eq!(dq.fold(), "a");
+/// ```
+///
+/// # Complexity
+/// - `push_front`, `push_back`, `fold`: O(1)
+/// - `pop_front`, `pop_back`: amortized O(1)
+#[derive(Debug, Clone)]
+pub struct FoldableDeque<T, F> {
+    front: Vec<(T, T)>,
+    back: Vec<(T, T)>,
+    op: F,
+    identity: T,
+}
+
+impl<T, F> FoldableDeque<T, F>
+where
+    T: Clone,
+    F: Fn(T, T) -> T,
+{
+    pub fn new(
+        op: F,
+        identity: T,
+    ) -> Self {
+        FoldableDeque {
+            front: Vec::new(),
+            back: Vec::new(),
+            op,
+            identity,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn push_front(
+        &mut self,
+        x: T,
+    ) {
+        let acc = match self.front.last() {
+            Some((_, acc)) => (self.op)(x.clone(), acc.clone()),
+            None => x.clone(),
+        };
+        self.front.push((x, acc));
+    }
+
+    pub fn push_back(
+        &mut self,
+        x: T,
+    ) {
+        let acc = match self.back.last() {
+            Some((_, acc)) => (self.op)(acc.clone(), x.clone()),
+            None => x.clone(),
+        };
+        self.back.push((x, acc));
+    }
+
+    // Rebuilds both stacks from the elements in front-to-back order,
+    // putting the first `mid` elements on the front stack.
+    fn rebuild(
+        &mut self,
+        items: Vec<T>,
+        mid: usize,
+    ) {
+        self.front.clear();
+        self.back.clear();
+        for x in items[..mid].iter().rev() {
+            self.push_front(x.clone());
+        }
+        for x in items[mid..].iter() {
+            self.push_back(x.clone());
+        }
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.front.is_empty() {
+            let items: Vec<T> = self.back.drain(..).map(|(x, _)| x).collect();
+            let mid = items.len().div_ceil(2);
+            self.rebuild(items, mid);
+        }
+        self.front.pop().map(|(x, _)| x)
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.back.is_empty() {
+            let items: Vec<T> = self.front.drain(..).rev().map(|(x, _)| x).collect();
+            let mid = items.len() / 2;
+            self.rebuild(items, mid);
+        }
+        self.back.pop().map(|(x, _)| x)
+    }
+
+    /// Returns the product of all elements from front to back (the identity if empty).
+    pub fn fold(&self) -> T {
+        let f = self.front.last().map_or(self.identity.clone(), |(_, acc)| acc.clone());
+        let b = self.back.last().map_or(self.identity.clone(), |(_, acc)| acc.clone());
+        (self.op)(f, b)
+    }
+}
+
+/// Returns the minimum of every window `a[i..i + k]` (`a.len() - k + 1` values) using a monotone deque.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(sliding_window_min(&[3, 1, 4, 1, 5, 9, 2], 3), vec![1, 1, 1, 1, 2]);
+/// ```
+///
+/// # Complexity
+/// - O(N)
+pub fn sliding_window_min<T: Ord + Clone>(
+    a: &[T],
+    k: usize,
+) -> Vec<T> {
+    sliding_window_by(a, k, |x, y| x <= y)
+}
+
+/// Returns the maximum of every window `a[i..i + k]` (`a.len() - k + 1` values) using a monotone deque.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(sliding_window_max(&[3, 1, 4, 1, 5, 9, 2], 3), vec![4, 4, 5, 9, 9]);
+/// ```
+///
+/// # Complexity
+/// - O(N)
+pub fn sliding_window_max<T: Ord + Clone>(
+    a: &[T],
+    k: usize,
+) -> Vec<T> {
+    sliding_window_by(a, k, |x, y| x >= y)
+}
+
+// `better(x, y)`: whether `x` makes `y` (an older element) useless.
+fn sliding_window_by<T: Clone>(
+    a: &[T],
+    k: usize,
+    better: impl Fn(&T, &T) -> bool,
+) -> Vec<T> {
+    assert!(k >= 1, "Window size must be positive");
+    let mut res = Vec::with_capacity((a.len() + 1).saturating_sub(k));
+    let mut dq: VecDeque<usize> = VecDeque::new();
+    for i in 0..a.len() {
+        while let Some(&j) = dq.back() {
+            if better(&a[i], &a[j]) {
+                dq.pop_back();
+            } else {
+                break;
+            }
+        }
+        dq.push_back(i);
+        if dq[0] + k <= i {
+            dq.pop_front();
+        }
+        if i + 1 >= k {
+            res.push(a[dq[0]].clone());
+        }
+    }
+    res
+}
+
+// --- SNAP END ---
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_foldable_deque_random() {
+        let mut seed: u64 = 0x0123_4567_89AB_CDEF;
+        let mut next = |m: u64| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed % m
+        };
+        // Non-commutative: affine composition modulo a prime
+        const P: u64 = 998244353;
+        let op = |f: (u64, u64), g: (u64, u64)| (f.0 * g.0 % P, (f.1 * g.0 + g.1) % P);
+        let mut q = FoldableQueue::new(op, (1, 0));
+        let mut dq = FoldableDeque::new(op, (1, 0));
+        let mut naive_q: VecDeque<(u64, u64)> = VecDeque::new();
+        let mut naive_dq: VecDeque<(u64, u64)> = VecDeque::new();
+        for _ in 0..3000 {
+            let f = (next(P), next(P));
+            match next(5) {
+                0 => {
+                    dq.push_front(f);
+                    naive_dq.push_front(f);
+                }
+                1 => {
+                    dq.push_back(f);
+                    naive_dq.push_back(f);
+                    q.push_back(f);
+                    naive_q.push_back(f);
+                }
+                2 => {
+                    assert_eq!(dq.pop_front(), naive_dq.pop_front());
+                    assert_eq!(q.pop_front(), naive_q.pop_front());
+                }
+                _ => {
+                    assert_eq!(dq.pop_back(), naive_dq.pop_back());
+                }
+            }
+            assert_eq!(dq.len(), naive_dq.len());
+            assert_eq!(q.len(), naive_q.len());
+            assert_eq!(dq.fold(), naive_dq.iter().fold((1, 0), |f, &g| op(f, g)));
+            assert_eq!(q.fold(), naive_q.iter().fold((1, 0), |f, &g| op(f, g)));
+        }
+    }
+
+    #[test]
+    fn test_sliding_window_min_max() {
+        let a = vec![5, 2, 2, 8, 1, 9, 3, 3, 7, 0];
+        for k in 1..=a.len() + 1 {
+            let min: Vec<i32> = a.windows(k).map(|w| *w.iter().min().unwrap()).collect();
+            let max: Vec<i32> = a.windows(k).map(|w| *w.iter().max().unwrap()).collect();
+            assert_eq!(sliding_window_min(&a, k), min);
+            assert_eq!(sliding_window_max(&a, k), max);
+        }
+    }
+}